@@ -0,0 +1,389 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Address Types
+//!
+//! Distinct types for virtual, physical and intermediate physical addresses. They prevent an address of one address
+//! space to be used where an address of another one is expected, for example passing a virtual address to a
+//! translation table descriptor.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::*;
+//! let va = VirtAddr::new(0xFFFF_0000_0008_1234);
+//! assert_eq!(va.page_down(Granule::_4KB), VirtAddr::new(0xFFFF_0000_0008_1000));
+//! assert_eq!(va.page_offset(Granule::_4KB), 0x234);
+//! // 48 bit virtual address space of the upper address range
+//! assert!(va.is_canonical(16, false));
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::register::el1::tcr_el1;
+use core::fmt;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// The translation granule sizes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Granule {
+  _4KB,
+  _16KB,
+  _64KB,
+}
+
+impl Granule {
+  /// The size of the granule in bytes
+  pub const fn size(self) -> u64 {
+    match self {
+      Granule::_4KB => 0x1000,
+      Granule::_16KB => 0x4000,
+      Granule::_64KB => 0x1_0000,
+    }
+  }
+}
+
+macro_rules! address_type {
+  ($(#[doc = $doc:expr])* $name:ident) => {
+    $(#[doc = $doc])*
+    #[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct $name(u64);
+
+    impl $name {
+      /// Create the address from its raw value
+      pub const fn new(addr: u64) -> Self {
+        Self(addr)
+      }
+
+      /// The raw value of the address
+      pub const fn as_u64(self) -> u64 {
+        self.0
+      }
+
+      /// Check whether the address is aligned to the given power of two
+      pub const fn is_aligned(self, align: u64) -> bool {
+        self.0 & (align - 1) == 0
+      }
+
+      /// Round the address down to the given power of two
+      pub const fn align_down(self, align: u64) -> Self {
+        Self(self.0 & !(align - 1))
+      }
+
+      /// Round the address up to the given power of two
+      ///
+      /// # Panics
+      /// If the rounded address exceeds ``u64::MAX``
+      pub const fn align_up(self, align: u64) -> Self {
+        match self.checked_align_up(align) {
+          Some(addr) => addr,
+          None => panic!("address overflow"),
+        }
+      }
+
+      /// Round the address up to the given power of two, ``None`` if the rounded address exceeds ``u64::MAX``
+      pub const fn checked_align_up(self, align: u64) -> Option<Self> {
+        match self.0.checked_add(align - 1) {
+          Some(addr) => Some(Self(addr & !(align - 1))),
+          None => None,
+        }
+      }
+
+      /// Add the offset to the address, ``None`` if the result exceeds ``u64::MAX``
+      pub const fn checked_add(self, offset: u64) -> Option<Self> {
+        match self.0.checked_add(offset) {
+          Some(addr) => Some(Self(addr)),
+          None => None,
+        }
+      }
+
+      /// Subtract the offset from the address, ``None`` if the result is below 0
+      pub const fn checked_sub(self, offset: u64) -> Option<Self> {
+        match self.0.checked_sub(offset) {
+          Some(addr) => Some(Self(addr)),
+          None => None,
+        }
+      }
+
+      /// The offset of the address within its page of the given granule
+      pub const fn page_offset(self, granule: Granule) -> u64 {
+        self.0 & (granule.size() - 1)
+      }
+
+      /// Round the address down to the start of its page of the given granule
+      pub const fn page_down(self, granule: Granule) -> Self {
+        self.align_down(granule.size())
+      }
+
+      /// Round the address up to the start of the next page of the given granule, if not page aligned already
+      ///
+      /// # Panics
+      /// If the start of the next page exceeds ``u64::MAX``
+      pub const fn page_up(self, granule: Granule) -> Self {
+        self.align_up(granule.size())
+      }
+
+      /// Round the address up to the start of the next page of the given granule, if not page aligned already.
+      /// ``None`` is returned if the start of the next page exceeds ``u64::MAX``
+      pub const fn checked_page_up(self, granule: Granule) -> Option<Self> {
+        self.checked_align_up(granule.size())
+      }
+    }
+
+    impl From<$name> for u64 {
+      fn from(addr: $name) -> u64 {
+        addr.0
+      }
+    }
+
+    /// Panics if the result exceeds ``u64::MAX``, use ``checked_add`` to handle this case
+    impl Add<u64> for $name {
+      type Output = Self;
+      fn add(self, offset: u64) -> Self {
+        self.checked_add(offset).expect("address overflow")
+      }
+    }
+
+    /// Panics if the result exceeds ``u64::MAX``
+    impl AddAssign<u64> for $name {
+      fn add_assign(&mut self, offset: u64) {
+        *self = *self + offset;
+      }
+    }
+
+    /// Panics if the result is below 0, use ``checked_sub`` to handle this case
+    impl Sub<u64> for $name {
+      type Output = Self;
+      fn sub(self, offset: u64) -> Self {
+        self.checked_sub(offset).expect("address underflow")
+      }
+    }
+
+    /// Panics if the result is below 0
+    impl SubAssign<u64> for $name {
+      fn sub_assign(&mut self, offset: u64) {
+        *self = *self - offset;
+      }
+    }
+
+    /// The distance between two addresses of the same address space
+    impl Sub<$name> for $name {
+      type Output = u64;
+      fn sub(self, other: $name) -> u64 {
+        self.0 - other.0
+      }
+    }
+
+    impl fmt::Debug for $name {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, concat!(stringify!($name), "({:#x})"), self.0)
+      }
+    }
+
+    impl fmt::LowerHex for $name {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+      }
+    }
+  };
+}
+
+address_type!(
+  /// A virtual address as used by software and translated by the stage 1 translation
+  VirtAddr
+);
+
+address_type!(
+  /// A physical address as output of the last stage of translation
+  PhysAddr
+);
+
+address_type!(
+  /// An intermediate physical address as output of the stage 1 and input of the stage 2 translation of a guest
+  IntermediatePhysAddr
+);
+
+impl VirtAddr {
+  /// The virtual address of the given pointer
+  pub fn from_ptr<T>(ptr: *const T) -> Self {
+    Self(ptr as u64)
+  }
+
+  /// The address as pointer
+  pub const fn as_ptr<T>(self) -> *const T {
+    self.0 as *const T
+  }
+
+  /// The address as mutable pointer
+  pub const fn as_mut_ptr<T>(self) -> *mut T {
+    self.0 as *mut T
+  }
+
+  /// Check whether the address lies in the upper address range translated with ``TTBR1_ELx``
+  pub const fn is_upper(self) -> bool {
+    self.0 & (1 << 55) != 0
+  }
+
+  /// Check whether the address is canonical for an address range with the given size offset ``TxSZ`` of the
+  /// ``TCR_ELx`` register, covering 2^(64-TxSZ) bytes. All bits above this size need to be equal to bit 55, if the top
+  /// byte is ignored (``TBI``) the bits \[63:56\] are not checked. A ``TxSZ`` exceeding 64 is treated as an empty
+  /// address range.
+  pub const fn is_canonical(self, txsz: u32, tbi: bool) -> bool {
+    let top = if tbi { 56 } else { 64 };
+    let bits = 64u32.saturating_sub(txsz);
+    if bits >= top {
+      return true;
+    }
+    let mask = (u64::MAX >> (64 - top)) & !((1 << bits) - 1);
+    let upper = self.0 & mask;
+    if self.is_upper() {
+      upper == mask
+    } else {
+      upper == 0
+    }
+  }
+
+  /// Check whether the address is canonical for the EL1&0 translation regime as configured in ``TCR_EL1``
+  #[cfg(target_arch = "aarch64")]
+  pub fn is_canonical_el1(self) -> bool {
+    if self.is_upper() {
+      self.is_canonical(
+        tcr_el1::read(tcr_el1::T1SZ::Field).value() as u32,
+        tcr_el1::read(tcr_el1::TBI1::Field) == tcr_el1::TBI1::IGNORE,
+      )
+    } else {
+      self.is_canonical(
+        tcr_el1::read(tcr_el1::T0SZ::Field).value() as u32,
+        tcr_el1::read(tcr_el1::TBI0::Field) == tcr_el1::TBI0::IGNORE,
+      )
+    }
+  }
+}
+
+/// An address that is the input of a translation stage, the virtual address of the stage 1 and the intermediate
+/// physical address of the stage 2 translation
+pub trait InputAddress: Copy {
+  /// The raw value of the address
+  fn as_u64(self) -> u64;
+}
+
+impl InputAddress for VirtAddr {
+  fn as_u64(self) -> u64 {
+    self.0
+  }
+}
+
+impl InputAddress for IntermediatePhysAddr {
+  fn as_u64(self) -> u64 {
+    self.0
+  }
+}
+
+impl<T> From<*const T> for VirtAddr {
+  fn from(ptr: *const T) -> Self {
+    Self::from_ptr(ptr)
+  }
+}
+
+impl<T> From<*mut T> for VirtAddr {
+  fn from(ptr: *mut T) -> Self {
+    Self::from_ptr(ptr)
+  }
+}
+
+impl PhysAddr {
+  /// The identity mapped virtual address of this physical address
+  pub const fn identity_mapped(self) -> VirtAddr {
+    VirtAddr(self.0)
+  }
+}
+
+impl IntermediatePhysAddr {
+  /// The physical address of an IPA of a guest whose stage 2 translation maps its IPA space one to one
+  pub const fn identity_mapped(self) -> PhysAddr {
+    PhysAddr(self.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn canonical_lower_range() {
+    assert!(VirtAddr::new(0x0000_FFFF_FFFF_FFFF).is_canonical(16, false));
+    assert!(!VirtAddr::new(0x0001_0000_0000_0000).is_canonical(16, false));
+    assert!(!VirtAddr::new(0xAB00_0000_0000_1000).is_canonical(16, false));
+    assert!(VirtAddr::new(0xAB00_0000_0000_1000).is_canonical(16, true));
+    assert!(VirtAddr::new(0x7F_FFFF_FFFF).is_canonical(25, false));
+    assert!(!VirtAddr::new(0x80_0000_0000).is_canonical(25, false));
+  }
+
+  #[test]
+  fn canonical_upper_range() {
+    assert!(VirtAddr::new(0xFFFF_0000_0000_0000).is_canonical(16, false));
+    assert!(!VirtAddr::new(0xFFFE_FFFF_FFFF_FFFF).is_canonical(16, false));
+    assert!(!VirtAddr::new(0x00FF_0000_0000_0000).is_canonical(16, false));
+    assert!(VirtAddr::new(0x00FF_0000_0000_0000).is_canonical(16, true));
+  }
+
+  #[test]
+  fn canonical_full_and_empty_range() {
+    assert!(VirtAddr::new(0x1234_5678_9ABC_DEF0).is_canonical(0, false));
+    assert!(VirtAddr::new(0).is_canonical(64, false));
+    assert!(VirtAddr::new(0).is_canonical(65, false));
+    assert!(!VirtAddr::new(0x1000).is_canonical(65, false));
+    assert!(!VirtAddr::new(0x1000).is_canonical(u32::MAX, true));
+  }
+
+  #[test]
+  fn page_alignment() {
+    let va = VirtAddr::new(0xFFFF_0000_0008_1234);
+    assert_eq!(
+      va.page_down(Granule::_4KB),
+      VirtAddr::new(0xFFFF_0000_0008_1000)
+    );
+    assert_eq!(
+      va.page_up(Granule::_16KB),
+      VirtAddr::new(0xFFFF_0000_0008_4000)
+    );
+    assert_eq!(va.page_offset(Granule::_64KB), 0x1234);
+  }
+
+  #[test]
+  fn align_up_near_the_end_of_the_address_space() {
+    let va = VirtAddr::new(0xFFFF_FFFF_FFFF_F001);
+    assert_eq!(va.checked_page_up(Granule::_4KB), None);
+    assert_eq!(
+      VirtAddr::new(0xFFFF_FFFF_FFFF_E001).checked_page_up(Granule::_4KB),
+      Some(VirtAddr::new(0xFFFF_FFFF_FFFF_F000))
+    );
+    assert_eq!(
+      VirtAddr::new(0xFFFF_FFFF_FFFF_F000).page_up(Granule::_4KB),
+      VirtAddr::new(0xFFFF_FFFF_FFFF_F000)
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "address overflow")]
+  fn page_up_overflow_panics() {
+    VirtAddr::new(0xFFFF_FFFF_FFFF_F001).page_up(Granule::_4KB);
+  }
+
+  #[test]
+  fn checked_arithmetic() {
+    let pa = PhysAddr::new(u64::MAX - 1);
+    assert_eq!(pa.checked_add(1), Some(PhysAddr::new(u64::MAX)));
+    assert_eq!(pa.checked_add(2), None);
+    assert_eq!(PhysAddr::new(1).checked_sub(2), None);
+    assert_eq!(PhysAddr::new(0x1000) - 0x10, PhysAddr::new(0xFF0));
+  }
+
+  #[test]
+  #[should_panic(expected = "address overflow")]
+  fn add_overflow_panics() {
+    let _ = VirtAddr::new(u64::MAX) + 1;
+  }
+}
@@ -0,0 +1,133 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Cache Maintenance
+//!
+//! Functions to clean and invalidate data and instruction caches by virtual address. The range functions operate on
+//! all cache lines covering the given memory region, using the smallest data cache line size from ``CTR_EL0``.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::VirtAddr;
+//! # use ruspiro_arch_aarch64::cache::*;
+//! static mut MAILBOX: [u32; 16] = [0; 16];
+//!
+//! // ensure the data is visible to a non-coherent observer, like the VideoCore or a core with caches disabled
+//! unsafe { clean_dcache_range_poc(VirtAddr::from_ptr(MAILBOX.as_ptr()), core::mem::size_of_val(&MAILBOX)) };
+//! ```
+#![cfg(target_arch = "aarch64")]
+
+use crate::addr::VirtAddr;
+use crate::instructions::{dsb, isb};
+use crate::register::el0::ctr_el0;
+use core::arch::asm;
+
+/// The smallest data cache line size in bytes of all data and unified caches
+#[inline]
+pub fn dcache_line_size() -> usize {
+  4 << ctr_el0::read(ctr_el0::DminLine::Field).value()
+}
+
+/// The smallest instruction cache line size in bytes of all instruction caches
+#[inline]
+pub fn icache_line_size() -> usize {
+  4 << ctr_el0::read(ctr_el0::IminLine::Field).value()
+}
+
+/// Clean the data cache line containing the address to the Point of Coherency
+#[inline]
+pub fn clean_dcache_line_poc(addr: VirtAddr) {
+  unsafe { asm!("dc cvac, {0}", in(reg) addr.as_u64()) };
+}
+
+/// Clean the data cache line containing the address to the Point of Unification
+#[inline]
+pub fn clean_dcache_line_pou(addr: VirtAddr) {
+  unsafe { asm!("dc cvau, {0}", in(reg) addr.as_u64()) };
+}
+
+/// Clean and invalidate the data cache line containing the address to the Point of Coherency
+#[inline]
+pub fn clean_invalidate_dcache_line_poc(addr: VirtAddr) {
+  unsafe { asm!("dc civac, {0}", in(reg) addr.as_u64()) };
+}
+
+/// Invalidate the data cache line containing the address to the Point of Coherency
+///
+/// # Safety
+/// Any modified data within the cache line that has not been cleaned is lost.
+#[inline]
+pub unsafe fn invalidate_dcache_line_poc(addr: VirtAddr) {
+  asm!("dc ivac, {0}", in(reg) addr.as_u64());
+}
+
+/// Invalidate the instruction cache line containing the address to the Point of Unification
+#[inline]
+pub fn invalidate_icache_line_pou(addr: VirtAddr) {
+  unsafe { asm!("ic ivau, {0}", in(reg) addr.as_u64()) };
+}
+
+/// Invalidate all instruction caches to the Point of Unification
+#[inline]
+pub fn invalidate_icache_all() {
+  unsafe { asm!("ic iallu") };
+  dsb();
+  isb();
+}
+
+/// Call the operation for each cache line covering the given region
+#[inline]
+fn for_each_line(start: VirtAddr, size: usize, line_size: usize, op: impl Fn(VirtAddr)) {
+  let mut addr = start.align_down(line_size as u64);
+  let end = start + size as u64;
+  while addr < end {
+    op(addr);
+    addr += line_size as u64;
+  }
+}
+
+/// Clean all data cache lines covering the given region to the Point of Coherency
+#[inline]
+pub fn clean_dcache_range_poc(start: VirtAddr, size: usize) {
+  for_each_line(start, size, dcache_line_size(), clean_dcache_line_poc);
+  dsb();
+}
+
+/// Clean and invalidate all data cache lines covering the given region to the Point of Coherency
+#[inline]
+pub fn clean_invalidate_dcache_range_poc(start: VirtAddr, size: usize) {
+  for_each_line(
+    start,
+    size,
+    dcache_line_size(),
+    clean_invalidate_dcache_line_poc,
+  );
+  dsb();
+}
+
+/// Invalidate all data cache lines covering the given region to the Point of Coherency
+///
+/// # Safety
+/// Any modified data within the cache lines that has not been cleaned is lost. This also affects data outside the
+/// region sharing a cache line with it.
+#[inline]
+pub unsafe fn invalidate_dcache_range_poc(start: VirtAddr, size: usize) {
+  for_each_line(start, size, dcache_line_size(), |addr| {
+    invalidate_dcache_line_poc(addr)
+  });
+  dsb();
+}
+
+/// Ensure the instructions written to the given region are visible to the instruction fetch. This cleans the data
+/// cache and invalidates the instruction cache to the Point of Unification.
+#[inline]
+pub fn sync_icache_range(start: VirtAddr, size: usize) {
+  for_each_line(start, size, dcache_line_size(), clean_dcache_line_pou);
+  dsb();
+  for_each_line(start, size, icache_line_size(), invalidate_icache_line_pou);
+  dsb();
+  isb();
+}
@@ -0,0 +1,241 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Cooperative Context Switch
+//!
+//! Provides the execution ``Context`` of a thread/task and the functions to switch between them. The context contains
+//! the callee-saved general purpose register x19-x30, the callee-saved SIMD&FP register d8-d15, the stack pointer and
+//! TPIDR_EL0 as defined by the AAPCS64. Any other register is saved by the caller of the ``switch`` function. As the
+//! context contains SIMD&FP register the access to them need to be enabled with ``CPACR_EL1::FPEN`` while switching.
+//!
+//! Only the lower 64Bit of v8-v15 are preserved. If tasks use the full SIMD&FP register file it need to be switched
+//! using the ``FpContext`` or ``LazyFp`` from the ``fp`` module.
+//!
+//! A scheduler using ``LazyFp`` need to switch with ``switch_without_fp`` or ``switch_with_tpidr_without_fp``. Those
+//! do not touch the SIMD&FP register, which are owned by the task holding the register file in ``LazyFp`` and are not
+//! accessible while the floating-point access is disabled after ``LazyFp::task_switched``. The ``d`` register of the
+//! ``Context`` are unused in this case. ``switch`` and ``switch_with_tpidr`` are used if all tasks share the SIMD&FP
+//! register file with the floating-point access enabled, or if the full register file is saved with ``FpContext``
+//! before the switch.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::context::*;
+//! static mut STACK: [u8; 4096] = [0; 4096];
+//! static mut MAIN: Context = Context::empty();
+//! static mut TASK: Context = Context::empty();
+//!
+//! extern "C" fn task(arg: usize) -> ! {
+//!     loop {
+//!         // yield back to the main context
+//!         unsafe { switch(&mut TASK, &MAIN) };
+//!     }
+//! }
+//!
+//! unsafe {
+//!     TASK = Context::new(task, 42, STACK.as_mut_ptr().add(STACK.len()));
+//!     switch(&mut MAIN, &TASK);
+//! }
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::global_asm;
+
+/// The execution context of a thread/task
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Context {
+  /// Callee-saved register x19-x28
+  pub x: [u64; 10],
+  /// Frame pointer x29
+  pub fp: u64,
+  /// Link register x30, the address the context resumes it's execution
+  pub lr: u64,
+  /// Stack pointer
+  pub sp: u64,
+  /// Software thread id register TPIDR_EL0, only switched with ``switch_with_tpidr``
+  pub tpidr_el0: u64,
+  /// Callee-saved SIMD&FP register d8-d15, unused by ``switch_without_fp`` and ``switch_with_tpidr_without_fp``
+  pub d: [u64; 8],
+}
+
+impl Context {
+  /// Create an empty context. This is typically used for the context a ``switch`` is initiated from as it will be
+  /// filled with the current state.
+  pub const fn empty() -> Self {
+    Self {
+      x: [0; 10],
+      fp: 0,
+      lr: 0,
+      sp: 0,
+      tpidr_el0: 0,
+      d: [0; 8],
+    }
+  }
+
+  /// Create a new context that will call ``entry`` with the ``arg`` given once it is switched to the first time. The
+  /// ``stack_top`` is the highest address of the stack to be used for this context and will be aligned to 16 bytes.
+  #[cfg(target_arch = "aarch64")]
+  pub fn new(entry: extern "C" fn(usize) -> !, arg: usize, stack_top: *mut u8) -> Self {
+    let mut context = Self::empty();
+    context.x[0] = entry as usize as u64;
+    context.x[1] = arg as u64;
+    context.lr = __ruspiro_context_entry as *const () as u64;
+    context.sp = (stack_top as u64) & !0xF;
+    context
+  }
+}
+
+/// Save the current execution state into ``from`` and continue execution with the state stored in ``to``. The
+/// function returns once another ``switch`` resumes the ``from`` context.
+///
+/// # Safety
+/// The ``to`` context need to be either created with ``Context::new`` or being filled by a previous ``switch``. Its
+/// stack need to stay valid as long as the context is in use.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub unsafe fn switch(from: &mut Context, to: &Context) {
+  __ruspiro_context_switch(from, to);
+}
+
+/// Same as ``switch`` but additionally saves and restores TPIDR_EL0, which typically holds the thread local storage
+/// pointer
+///
+/// # Safety
+/// See ``switch``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub unsafe fn switch_with_tpidr(from: &mut Context, to: &Context) {
+  __ruspiro_context_switch_tpidr(from, to);
+}
+
+/// Same as ``switch`` but without saving and restoring the SIMD&FP register d8-d15, to be used together with
+/// ``LazyFp``
+///
+/// # Safety
+/// See ``switch``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub unsafe fn switch_without_fp(from: &mut Context, to: &Context) {
+  __ruspiro_context_switch_gp(from, to);
+}
+
+/// Same as ``switch_with_tpidr`` but without saving and restoring the SIMD&FP register d8-d15, to be used together
+/// with ``LazyFp``
+///
+/// # Safety
+/// See ``switch``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub unsafe fn switch_with_tpidr_without_fp(from: &mut Context, to: &Context) {
+  __ruspiro_context_switch_gp_tpidr(from, to);
+}
+
+#[cfg(target_arch = "aarch64")]
+extern "C" {
+  fn __ruspiro_context_switch(from: *mut Context, to: *const Context);
+  fn __ruspiro_context_switch_tpidr(from: *mut Context, to: *const Context);
+  fn __ruspiro_context_switch_gp(from: *mut Context, to: *const Context);
+  fn __ruspiro_context_switch_gp_tpidr(from: *mut Context, to: *const Context);
+  fn __ruspiro_context_entry();
+}
+
+#[cfg(target_arch = "aarch64")]
+global_asm!(
+  ".section .text.__ruspiro_context_switch, \"ax\"",
+  ".global __ruspiro_context_switch",
+  ".type __ruspiro_context_switch, %function",
+  ".balign 4",
+  "__ruspiro_context_switch:",
+  // save the callee-saved SIMD&FP register of the current context and restore the ones of the next context
+  "  stp d8, d9, [x0, #112]",
+  "  stp d10, d11, [x0, #128]",
+  "  stp d12, d13, [x0, #144]",
+  "  stp d14, d15, [x0, #160]",
+  "  ldp d8, d9, [x1, #112]",
+  "  ldp d10, d11, [x1, #128]",
+  "  ldp d12, d13, [x1, #144]",
+  "  ldp d14, d15, [x1, #160]",
+  "  b __ruspiro_context_switch_gp",
+  ".size __ruspiro_context_switch, . - __ruspiro_context_switch",
+  "",
+  ".section .text.__ruspiro_context_switch_tpidr, \"ax\"",
+  ".global __ruspiro_context_switch_tpidr",
+  ".type __ruspiro_context_switch_tpidr, %function",
+  ".balign 4",
+  "__ruspiro_context_switch_tpidr:",
+  "  mrs x9, tpidr_el0",
+  "  str x9, [x0, #104]",
+  "  ldr x9, [x1, #104]",
+  "  msr tpidr_el0, x9",
+  "  b __ruspiro_context_switch",
+  ".size __ruspiro_context_switch_tpidr, . - __ruspiro_context_switch_tpidr",
+  "",
+  ".section .text.__ruspiro_context_switch_gp, \"ax\"",
+  ".global __ruspiro_context_switch_gp",
+  ".type __ruspiro_context_switch_gp, %function",
+  ".balign 4",
+  "__ruspiro_context_switch_gp:",
+  // save the callee-saved general purpose register of the current context
+  "  stp x19, x20, [x0, #0]",
+  "  stp x21, x22, [x0, #16]",
+  "  stp x23, x24, [x0, #32]",
+  "  stp x25, x26, [x0, #48]",
+  "  stp x27, x28, [x0, #64]",
+  "  stp x29, x30, [x0, #80]",
+  "  mov x9, sp",
+  "  str x9, [x0, #96]",
+  // restore the callee-saved general purpose register of the next context
+  "  ldp x19, x20, [x1, #0]",
+  "  ldp x21, x22, [x1, #16]",
+  "  ldp x23, x24, [x1, #32]",
+  "  ldp x25, x26, [x1, #48]",
+  "  ldp x27, x28, [x1, #64]",
+  "  ldp x29, x30, [x1, #80]",
+  "  ldr x9, [x1, #96]",
+  "  mov sp, x9",
+  "  ret",
+  ".size __ruspiro_context_switch_gp, . - __ruspiro_context_switch_gp",
+  "",
+  ".section .text.__ruspiro_context_switch_gp_tpidr, \"ax\"",
+  ".global __ruspiro_context_switch_gp_tpidr",
+  ".type __ruspiro_context_switch_gp_tpidr, %function",
+  ".balign 4",
+  "__ruspiro_context_switch_gp_tpidr:",
+  "  mrs x9, tpidr_el0",
+  "  str x9, [x0, #104]",
+  "  ldr x9, [x1, #104]",
+  "  msr tpidr_el0, x9",
+  "  b __ruspiro_context_switch_gp",
+  ".size __ruspiro_context_switch_gp_tpidr, . - __ruspiro_context_switch_gp_tpidr",
+  "",
+  // initial entry of a new context: call the entry function stored in x19 with the argument stored in x20
+  ".section .text.__ruspiro_context_entry, \"ax\"",
+  ".global __ruspiro_context_entry",
+  ".type __ruspiro_context_entry, %function",
+  ".balign 4",
+  "__ruspiro_context_entry:",
+  "  mov x0, x20",
+  "  mov x29, xzr",
+  "  mov x30, xzr",
+  "  br x19",
+  ".size __ruspiro_context_entry, . - __ruspiro_context_entry",
+);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn layout_matches_assembly() {
+    let context = Context::empty();
+    let base = &context as *const Context as usize;
+    assert_eq!(&context.sp as *const u64 as usize - base, 96);
+    assert_eq!(&context.tpidr_el0 as *const u64 as usize - base, 104);
+    assert_eq!(context.d.as_ptr() as usize - base, 112);
+    assert_eq!(core::mem::size_of::<Context>(), 176);
+  }
+}
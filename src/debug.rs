@@ -0,0 +1,266 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Hardware Breakpoints and Watchpoints
+//!
+//! Program the self-hosted debug breakpoint and watchpoint registers of the current core. A hit of a breakpoint or
+//! watchpoint raises a debug exception (exception class ``BREAKPOINT_*`` or ``WATCHPOINT_*``) at the exception level
+//! debug exceptions are routed to. The number of breakpoints and watchpoints is implementation defined and discovered
+//! from ``ID_AA64DFR0_EL1``. The slots are handed out by checking the enable bit of the control registers, so the
+//! debug registers of each core are managed independently.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::VirtAddr;
+//! # use ruspiro_arch_aarch64::debug::*;
+//! static mut COUNTER: u32 = 0;
+//!
+//! // debug exceptions are also taken from code running at EL1
+//! enable(true);
+//! let watchpoint = set_watchpoint(
+//!   VirtAddr::from_ptr(unsafe { core::ptr::addr_of!(COUNTER) }),
+//!   4,
+//!   Access::Write,
+//! )
+//! .unwrap();
+//! /* the next write to COUNTER raises a watchpoint exception */
+//! clear_watchpoint(watchpoint);
+//! ```
+
+use crate::addr::VirtAddr;
+#[cfg(target_arch = "aarch64")]
+use crate::instructions::isb;
+use crate::register::el1::dbgwcr_el1;
+#[cfg(target_arch = "aarch64")]
+use crate::register::el1::{
+  dbgbcr_el1, dbgbvr_el1, dbgwvr_el1, id_aa64dfr0_el1, mdscr_el1, osdlr_el1, oslar_el1,
+};
+#[cfg(target_arch = "aarch64")]
+use core::arch::asm;
+
+/// The kind of data access a watchpoint matches
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+  Read,
+  Write,
+  ReadWrite,
+}
+
+/// The reasons a breakpoint or watchpoint could not be set
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DebugError {
+  /// All breakpoints or watchpoints of the current core are in use
+  NoneAvailable,
+  /// The breakpoint address is not aligned to an instruction
+  Unaligned,
+  /// The watched range is neither located within a single double word nor a naturally aligned power of two
+  UnsupportedRange,
+}
+
+/// A breakpoint set on the current core
+#[derive(Debug, PartialEq, Eq)]
+pub struct Breakpoint(usize);
+
+impl Breakpoint {
+  /// The number of the breakpoint registers used
+  pub fn index(&self) -> usize {
+    self.0
+  }
+}
+
+/// A watchpoint set on the current core
+#[derive(Debug, PartialEq, Eq)]
+pub struct Watchpoint(usize);
+
+impl Watchpoint {
+  /// The number of the watchpoint registers used
+  pub fn index(&self) -> usize {
+    self.0
+  }
+}
+
+/// The number of breakpoints implemented
+#[cfg(target_arch = "aarch64")]
+pub fn breakpoint_count() -> usize {
+  id_aa64dfr0_el1::read(id_aa64dfr0_el1::BRPs::Field).value() as usize + 1
+}
+
+/// The number of watchpoints implemented
+#[cfg(target_arch = "aarch64")]
+pub fn watchpoint_count() -> usize {
+  id_aa64dfr0_el1::read(id_aa64dfr0_el1::WRPs::Field).value() as usize + 1
+}
+
+/// Enable breakpoint and watchpoint debug exceptions on the current core. The OS Lock and the OS Double Lock are
+/// released. With ``kernel`` set debug exceptions are also generated at the exception level they are taken to, which
+/// requires ``PSTATE.D`` to be cleared, done here for the current context. Otherwise they are disabled there.
+#[cfg(target_arch = "aarch64")]
+pub fn enable(kernel: bool) {
+  osdlr_el1::write(osdlr_el1::DLK::UNLOCK);
+  oslar_el1::set(oslar_el1::OSLK::UNLOCK.raw_value());
+  isb();
+  mdscr_el1::write(mdscr_el1::MDE::ENABLE);
+  if kernel {
+    mdscr_el1::write(mdscr_el1::KDE::ENABLE);
+  } else {
+    mdscr_el1::write(mdscr_el1::KDE::DISABLE);
+  }
+  isb();
+  if kernel {
+    unsafe { asm!("msr daifclr, #8") };
+  }
+}
+
+/// Disable breakpoint and watchpoint debug exceptions on the current core
+#[cfg(target_arch = "aarch64")]
+pub fn disable() {
+  mdscr_el1::write(mdscr_el1::MDE::DISABLE);
+  mdscr_el1::write(mdscr_el1::KDE::DISABLE);
+  isb();
+}
+
+/// Calculate the value and control register contents of a watchpoint covering ``len`` bytes starting at ``addr``. A
+/// range within a single double word is selected with the byte address select bits, a larger range need to be a
+/// naturally aligned power of two of up to 2GB and is selected with the address mask. The control value has the
+/// enable bit set and matches accesses from EL1 and EL0.
+pub fn watchpoint_control(
+  addr: VirtAddr,
+  len: usize,
+  access: Access,
+) -> Result<(u64, u64), DebugError> {
+  let (value, bas, mask) = match len {
+    1..=8 if (addr.as_u64() & 7) + len as u64 <= 8 => {
+      let bas = ((1 << len) - 1) << (addr.as_u64() & 7);
+      (addr.align_down(8), bas, 0)
+    }
+    _ if len.is_power_of_two() && len > 8 && len <= 1 << 31 && addr.is_aligned(len as u64) => {
+      (addr, 0xFF, len.trailing_zeros() as u64)
+    }
+    _ => return Err(DebugError::UnsupportedRange),
+  };
+  let lsc = match access {
+    Access::Read => dbgwcr_el1::LSC::LOAD,
+    Access::Write => dbgwcr_el1::LSC::STORE,
+    Access::ReadWrite => dbgwcr_el1::LSC::LOAD_STORE,
+  };
+  let control = (dbgwcr_el1::E::ENABLE
+    | dbgwcr_el1::PAC::EL1_EL0
+    | lsc
+    | dbgwcr_el1::BAS::with_value(bas)
+    | dbgwcr_el1::MASK::with_value(mask))
+  .raw_value();
+  Ok((value.as_u64(), control))
+}
+
+/// Set a breakpoint on the instruction at ``addr``, matching instruction fetches at EL1 and EL0
+#[cfg(target_arch = "aarch64")]
+pub fn set_breakpoint(addr: VirtAddr) -> Result<Breakpoint, DebugError> {
+  if !addr.is_aligned(4) {
+    return Err(DebugError::Unaligned);
+  }
+  let index = (0..breakpoint_count())
+    .find(|&n| dbgbcr_el1::get_indexed(n) & dbgbcr_el1::E::Field.mask() == 0)
+    .ok_or(DebugError::NoneAvailable)?;
+  dbgbvr_el1::set_indexed(index, addr.as_u64());
+  dbgbcr_el1::set_indexed(
+    index,
+    (dbgbcr_el1::E::ENABLE
+      | dbgbcr_el1::PMC::EL1_EL0
+      | dbgbcr_el1::BAS::A64
+      | dbgbcr_el1::BT::ADDRESS)
+      .raw_value(),
+  );
+  isb();
+  Ok(Breakpoint(index))
+}
+
+/// Remove the breakpoint, freeing its registers
+#[cfg(target_arch = "aarch64")]
+pub fn clear_breakpoint(breakpoint: Breakpoint) {
+  dbgbcr_el1::set_indexed(breakpoint.0, 0);
+  isb();
+}
+
+/// Set a watchpoint on the ``len`` bytes starting at ``addr``, matching the given data accesses at EL1 and EL0. See
+/// ``watchpoint_control`` for the ranges supported.
+#[cfg(target_arch = "aarch64")]
+pub fn set_watchpoint(
+  addr: VirtAddr,
+  len: usize,
+  access: Access,
+) -> Result<Watchpoint, DebugError> {
+  let (value, control) = watchpoint_control(addr, len, access)?;
+  let index = (0..watchpoint_count())
+    .find(|&n| dbgwcr_el1::get_indexed(n) & dbgwcr_el1::E::Field.mask() == 0)
+    .ok_or(DebugError::NoneAvailable)?;
+  dbgwvr_el1::set_indexed(index, value);
+  dbgwcr_el1::set_indexed(index, control);
+  isb();
+  Ok(Watchpoint(index))
+}
+
+/// Remove the watchpoint, freeing its registers
+#[cfg(target_arch = "aarch64")]
+pub fn clear_watchpoint(watchpoint: Watchpoint) {
+  dbgwcr_el1::set_indexed(watchpoint.0, 0);
+  isb();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn bas(control: u64) -> u64 {
+    (control >> 5) & 0xFF
+  }
+
+  fn mask(control: u64) -> u64 {
+    (control >> 24) & 0x1F
+  }
+
+  #[test]
+  fn unaligned_range_within_double_word() {
+    let (value, control) = watchpoint_control(VirtAddr::new(0x1003), 2, Access::Write).unwrap();
+    assert_eq!(value, 0x1000);
+    assert_eq!(bas(control), 0b11 << 3);
+    assert_eq!(mask(control), 0);
+    assert_eq!(
+      control,
+      (dbgwcr_el1::E::ENABLE
+        | dbgwcr_el1::PAC::EL1_EL0
+        | dbgwcr_el1::LSC::STORE
+        | dbgwcr_el1::BAS::with_value(0b11 << 3))
+      .raw_value()
+    );
+  }
+
+  #[test]
+  fn range_crossing_double_word_is_rejected() {
+    assert_eq!(
+      watchpoint_control(VirtAddr::new(0x1007), 2, Access::Read),
+      Err(DebugError::UnsupportedRange)
+    );
+  }
+
+  #[test]
+  fn large_range_is_masked() {
+    let (value, control) =
+      watchpoint_control(VirtAddr::new(0x4000_3000), 0x1000, Access::ReadWrite).unwrap();
+    assert_eq!(value, 0x4000_3000);
+    assert_eq!(bas(control), 0xFF);
+    assert_eq!(mask(control), 12);
+  }
+
+  #[test]
+  fn unsupported_ranges_are_rejected() {
+    for (addr, len) in [(0x1000, 0), (0x1000, 24), (0x1800, 0x1000), (0, 1 << 32)] {
+      assert_eq!(
+        watchpoint_control(VirtAddr::new(addr), len, Access::Read),
+        Err(DebugError::UnsupportedRange)
+      );
+    }
+  }
+}
@@ -0,0 +1,105 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Exception Handling
+//!
+//! Shared definitions used by exception handlers. The ``TrapFrame`` holds the register state of the interrupted
+//! context as saved by the exception entry code, the ``ec`` module provides the exception classes as reported in the
+//! ``ESR_ELx::EC`` field.
+
+/// The exception classes as reported in ``ESR_ELx::EC``
+pub mod ec {
+  /// Unknown reason
+  pub const UNKNOWN: u32 = 0x00;
+  /// Trapped WFI or WFE instruction
+  pub const WFX: u32 = 0x01;
+  /// Access to SIMD or floating-point functionality trapped by ``CPACR_EL1::FPEN``, ``CPTR_EL2`` or ``CPTR_EL3``
+  pub const FP_ACCESS: u32 = 0x07;
+  /// Illegal execution state
+  pub const ILLEGAL_STATE: u32 = 0x0E;
+  /// SVC instruction execution in AArch64 state
+  pub const SVC64: u32 = 0x15;
+  /// HVC instruction execution in AArch64 state
+  pub const HVC64: u32 = 0x16;
+  /// SMC instruction execution in AArch64 state
+  pub const SMC64: u32 = 0x17;
+  /// Trapped MSR, MRS or system instruction execution in AArch64 state
+  pub const SYSREG: u32 = 0x18;
+  /// Instruction abort from a lower exception level
+  pub const IABT_LOWER: u32 = 0x20;
+  /// Instruction abort taken without a change in exception level
+  pub const IABT_CURRENT: u32 = 0x21;
+  /// PC alignment fault
+  pub const PC_ALIGNMENT: u32 = 0x22;
+  /// Data abort from a lower exception level
+  pub const DABT_LOWER: u32 = 0x24;
+  /// Data abort taken without a change in exception level
+  pub const DABT_CURRENT: u32 = 0x25;
+  /// SP alignment fault
+  pub const SP_ALIGNMENT: u32 = 0x26;
+  /// Trapped floating-point exception in AArch64 state
+  pub const FP_EXCEPTION64: u32 = 0x2C;
+  /// SError interrupt
+  pub const SERROR: u32 = 0x2F;
+  /// Breakpoint exception from a lower exception level
+  pub const BREAKPOINT_LOWER: u32 = 0x30;
+  /// Breakpoint exception taken without a change in exception level
+  pub const BREAKPOINT_CURRENT: u32 = 0x31;
+  /// Software step exception from a lower exception level
+  pub const SOFTWARE_STEP_LOWER: u32 = 0x32;
+  /// Software step exception taken without a change in exception level
+  pub const SOFTWARE_STEP_CURRENT: u32 = 0x33;
+  /// Watchpoint exception from a lower exception level
+  pub const WATCHPOINT_LOWER: u32 = 0x34;
+  /// Watchpoint exception taken without a change in exception level
+  pub const WATCHPOINT_CURRENT: u32 = 0x35;
+  /// BRK instruction execution in AArch64 state
+  pub const BRK64: u32 = 0x3C;
+}
+
+/// The register state of the interrupted context saved on exception entry
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrapFrame {
+  /// General purpose register x0-x30
+  pub x: [u64; 31],
+  /// The return address from ``ELR_ELx``
+  pub elr: u64,
+  /// The saved process state from ``SPSR_ELx``
+  pub spsr: u64,
+}
+
+impl TrapFrame {
+  /// Create an empty trap frame
+  pub const fn new() -> Self {
+    Self {
+      x: [0; 31],
+      elr: 0,
+      spsr: 0,
+    }
+  }
+
+  /// Read the general purpose register with the given number as encoded in instructions. The number 31 refers to the
+  /// zero register and always returns 0.
+  #[inline]
+  pub fn reg(&self, num: usize) -> u64 {
+    if num < 31 {
+      self.x[num]
+    } else {
+      0
+    }
+  }
+
+  /// Write the general purpose register with the given number as encoded in instructions. Writes to the zero
+  /// register 31 are ignored.
+  #[inline]
+  pub fn set_reg(&mut self, num: usize, value: u64) {
+    if num < 31 {
+      self.x[num] = value;
+    }
+  }
+}
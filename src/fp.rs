@@ -0,0 +1,361 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Floating Point Support
+//!
+//! Functions to save and restore the floating-point control state and to query and clear the cumulative
+//! floating-point exception flags. The ``FpContext`` allows to save and restore the whole floating-point register file
+//! and the ``LazyFp`` switches this register file only if a task actually uses floating-point functionality. Access to
+//! the floating-point registers need to be enabled with ``CPACR_EL1::FPEN`` for the current exception level, otherwise
+//! it will trap.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::fp::*;
+//! # use ruspiro_arch_aarch64::register::fpcr;
+//! let state = FpControlState::save();
+//! fpcr::write(fpcr::RMode::RZ);
+//!
+//! /* do some floating point calculation */
+//!
+//! if exception_flags().contains(FpExceptions::DIVIDE_BY_ZERO) {
+//!     /* handle the division by zero */
+//! }
+//! state.restore();
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::instructions::isb;
+#[cfg(target_arch = "aarch64")]
+use crate::register::{el1::cpacr_el1, fpcr, fpsr};
+use core::ops::BitOr;
+
+/// The floating-point control and status state
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FpControlState {
+  /// Contents of the ``FPCR`` register
+  pub fpcr: u64,
+  /// Contents of the ``FPSR`` register
+  pub fpsr: u64,
+}
+
+impl FpControlState {
+  /// Save the current floating-point control and status state
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn save() -> Self {
+    Self {
+      fpcr: fpcr::get(),
+      fpsr: fpsr::get(),
+    }
+  }
+
+  /// Restore the floating-point control and status state
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn restore(&self) {
+    fpcr::set(self.fpcr);
+    fpsr::set(self.fpsr);
+  }
+}
+
+/// The set of cumulative floating-point exception flags as reported in the ``FPSR`` register
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FpExceptions(u64);
+
+impl FpExceptions {
+  /// No exception flag set
+  pub const NONE: Self = Self(0);
+  /// Invalid operation
+  pub const INVALID_OPERATION: Self = Self(1 << 0);
+  /// Divide by zero
+  pub const DIVIDE_BY_ZERO: Self = Self(1 << 1);
+  /// Overflow
+  pub const OVERFLOW: Self = Self(1 << 2);
+  /// Underflow
+  pub const UNDERFLOW: Self = Self(1 << 3);
+  /// Inexact
+  pub const INEXACT: Self = Self(1 << 4);
+  /// Input denormal
+  pub const INPUT_DENORMAL: Self = Self(1 << 7);
+  /// All exception flags
+  pub const ALL: Self = Self(0b1001_1111);
+
+  /// Create the set of exception flags from a raw ``FPSR`` value
+  pub const fn from_fpsr(fpsr: u64) -> Self {
+    Self(fpsr & Self::ALL.0)
+  }
+
+  /// The raw bits of the exception flags as they are stored in the ``FPSR`` register
+  pub const fn bits(&self) -> u64 {
+    self.0
+  }
+
+  /// Check whether no exception flag is set
+  pub const fn is_empty(&self) -> bool {
+    self.0 == 0
+  }
+
+  /// Check whether all flags of ``other`` are set
+  pub const fn contains(&self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+}
+
+impl BitOr for FpExceptions {
+  type Output = Self;
+
+  fn bitor(self, rhs: Self) -> Self {
+    Self(self.0 | rhs.0)
+  }
+}
+
+/// Read the cumulative floating-point exception flags that has been raised since they were cleared the last time
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn exception_flags() -> FpExceptions {
+  FpExceptions::from_fpsr(fpsr::get())
+}
+
+/// Clear the given cumulative floating-point exception flags
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn clear_exception_flags(flags: FpExceptions) {
+  fpsr::set(fpsr::get() & !flags.bits());
+}
+
+/// Read and clear all cumulative floating-point exception flags
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn take_exception_flags() -> FpExceptions {
+  let raw_value = fpsr::get();
+  fpsr::set(raw_value & !FpExceptions::ALL.bits());
+  FpExceptions::from_fpsr(raw_value)
+}
+
+/// The complete floating-point register file. This contains the SIMD&FP registers V0-V31 together with the ``FPCR``
+/// and ``FPSR`` register.
+#[repr(C, align(16))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FpContext {
+  /// Contents of the SIMD&FP register V0-V31
+  pub v: [u128; 32],
+  /// Contents of the ``FPCR`` register
+  pub fpcr: u64,
+  /// Contents of the ``FPSR`` register
+  pub fpsr: u64,
+}
+
+impl FpContext {
+  /// Create a new floating-point context with all register cleared
+  pub const fn new() -> Self {
+    Self {
+      v: [0; 32],
+      fpcr: 0,
+      fpsr: 0,
+    }
+  }
+
+  /// Save the current floating-point register file into this context
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn save(&mut self) {
+    unsafe {
+      core::arch::asm!(
+        "stp q0, q1, [{ctx}, #0]",
+        "stp q2, q3, [{ctx}, #32]",
+        "stp q4, q5, [{ctx}, #64]",
+        "stp q6, q7, [{ctx}, #96]",
+        "stp q8, q9, [{ctx}, #128]",
+        "stp q10, q11, [{ctx}, #160]",
+        "stp q12, q13, [{ctx}, #192]",
+        "stp q14, q15, [{ctx}, #224]",
+        "stp q16, q17, [{ctx}, #256]",
+        "stp q18, q19, [{ctx}, #288]",
+        "stp q20, q21, [{ctx}, #320]",
+        "stp q22, q23, [{ctx}, #352]",
+        "stp q24, q25, [{ctx}, #384]",
+        "stp q26, q27, [{ctx}, #416]",
+        "stp q28, q29, [{ctx}, #448]",
+        "stp q30, q31, [{ctx}, #480]",
+        "mrs {tmp}, fpcr",
+        "str {tmp}, [{ctx}, #512]",
+        "mrs {tmp}, fpsr",
+        "str {tmp}, [{ctx}, #520]",
+        ctx = in(reg) self as *mut Self,
+        tmp = out(reg) _,
+      )
+    };
+  }
+
+  /// Restore the floating-point register file from this context
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn restore(&self) {
+    unsafe {
+      core::arch::asm!(
+        "ldp q0, q1, [{ctx}, #0]",
+        "ldp q2, q3, [{ctx}, #32]",
+        "ldp q4, q5, [{ctx}, #64]",
+        "ldp q6, q7, [{ctx}, #96]",
+        "ldp q8, q9, [{ctx}, #128]",
+        "ldp q10, q11, [{ctx}, #160]",
+        "ldp q12, q13, [{ctx}, #192]",
+        "ldp q14, q15, [{ctx}, #224]",
+        "ldp q16, q17, [{ctx}, #256]",
+        "ldp q18, q19, [{ctx}, #288]",
+        "ldp q20, q21, [{ctx}, #320]",
+        "ldp q22, q23, [{ctx}, #352]",
+        "ldp q24, q25, [{ctx}, #384]",
+        "ldp q26, q27, [{ctx}, #416]",
+        "ldp q28, q29, [{ctx}, #448]",
+        "ldp q30, q31, [{ctx}, #480]",
+        "ldr {tmp}, [{ctx}, #512]",
+        "msr fpcr, {tmp}",
+        "ldr {tmp}, [{ctx}, #520]",
+        "msr fpsr, {tmp}",
+        ctx = in(reg) self as *const Self,
+        tmp = out(reg) _,
+        out("v0") _,
+        out("v1") _,
+        out("v2") _,
+        out("v3") _,
+        out("v4") _,
+        out("v5") _,
+        out("v6") _,
+        out("v7") _,
+        out("v8") _,
+        out("v9") _,
+        out("v10") _,
+        out("v11") _,
+        out("v12") _,
+        out("v13") _,
+        out("v14") _,
+        out("v15") _,
+        out("v16") _,
+        out("v17") _,
+        out("v18") _,
+        out("v19") _,
+        out("v20") _,
+        out("v21") _,
+        out("v22") _,
+        out("v23") _,
+        out("v24") _,
+        out("v25") _,
+        out("v26") _,
+        out("v27") _,
+        out("v28") _,
+        out("v29") _,
+        out("v30") _,
+        out("v31") _,
+      )
+    };
+  }
+}
+
+impl Default for FpContext {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Enable the access to Advanced SIMD and floating-point functionality at EL0 and EL1
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn enable_fp() {
+  cpacr_el1::write(cpacr_el1::FPEN::NO_TRAP);
+  isb();
+}
+
+/// Disable the access to Advanced SIMD and floating-point functionality at EL0 and EL1. Any access will trap to EL1
+/// with the exception class ``exception::ec::FP_ACCESS``.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn disable_fp() {
+  cpacr_el1::write(cpacr_el1::FPEN::TRAP_ALL);
+  isb();
+}
+
+/// Lazy switching of the floating-point register file at EL1. The register file is only switched once a task actually
+/// uses the floating-point functionality after it has been scheduled.
+///
+/// The scheduler calls ``task_switched`` on each task switch, which disables the floating-point access. The first
+/// access of the new task traps and the exception handler calls ``handle_trap`` with the floating-point context of
+/// the current task. This saves the register file into the context of the previous owner and restores it from the
+/// current one. The task switch itself need to leave the SIMD&FP register untouched, which is done by
+/// ``context::switch_without_fp`` and ``context::switch_with_tpidr_without_fp``.
+///
+/// ```no_run
+/// # use ruspiro_arch_aarch64::fp::*;
+/// # use ruspiro_arch_aarch64::register::el1::esr_el1;
+/// static mut LAZY_FP: LazyFp = LazyFp::new();
+/// # let mut current_task_fp = FpContext::new();
+///
+/// // within the synchronous exception handler
+/// let ec = esr_el1::read(esr_el1::EC::Field).value();
+/// if unsafe { LAZY_FP.handle_trap(ec, &mut current_task_fp) } {
+///     /* return to the faulting instruction */
+/// }
+/// ```
+pub struct LazyFp {
+  owner: *mut FpContext,
+}
+
+impl LazyFp {
+  /// Create a new lazy floating-point switcher with no current owner of the register file
+  pub const fn new() -> Self {
+    Self {
+      owner: core::ptr::null_mut(),
+    }
+  }
+
+  /// Notify a task switch. This disables the floating-point access so the next usage will trap
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn task_switched(&self) {
+    disable_fp();
+  }
+
+  /// Handle a trapped floating-point access with the exception class ``ec`` taken from ``ESR_EL1``. Returns
+  /// ``false`` if the exception was not caused by a floating-point access. Otherwise the floating-point access is
+  /// enabled and the register file is switched to the ``current`` context if it is not already the owner.
+  ///
+  /// # Safety
+  /// The ``current`` context is kept as owner of the register file. It need to stay valid until it is released with
+  /// ``release`` or another context took over the ownership.
+  #[cfg(target_arch = "aarch64")]
+  pub unsafe fn handle_trap(&mut self, ec: u32, current: *mut FpContext) -> bool {
+    if ec != crate::exception::ec::FP_ACCESS {
+      return false;
+    }
+
+    enable_fp();
+    if self.owner != current {
+      if let Some(owner) = self.owner.as_mut() {
+        owner.save();
+      }
+      (*current).restore();
+      self.owner = current;
+    }
+
+    true
+  }
+
+  /// Release the ownership of the register file of the given context, for example if the task owning the context
+  /// has been terminated
+  #[inline]
+  pub fn release(&mut self, context: *mut FpContext) {
+    if self.owner == context {
+      self.owner = core::ptr::null_mut();
+    }
+  }
+}
+
+impl Default for LazyFp {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -9,6 +9,8 @@
 //!
 //! Functions to emit specific assembly instructions
 //!
+#![cfg(target_arch = "aarch64")]
+
 use core::arch::asm;
 
 /// assembly NOP instruction
@@ -0,0 +1,122 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Interrupt Masking
+//!
+//! Functions to mask and unmask interrupts at the current exception level using the immediate forms of the
+//! ``DAIFSet`` and ``DAIFClr`` instructions. The ``InterruptGuard`` masks IRQ and FIQ for the time it is alive and
+//! restores the previous state once it is dropped.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::interrupt::*;
+//! {
+//!     let _guard = InterruptGuard::acquire();
+//!     /* access data shared with an interrupt handler */
+//! }
+//! // IRQ and FIQ are restored to the state before the guard has been acquired
+//! ```
+//!
+//! With the feature ``critical-section-single-core`` this guard is used to implement the ``critical-section`` crate
+//! for single core systems.
+#![cfg(target_arch = "aarch64")]
+
+use crate::register::daif;
+use core::arch::asm;
+
+/// Mask IRQ's at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn mask_irq() {
+  unsafe { asm!("msr daifset, #2") };
+}
+
+/// Unmask IRQ's at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn unmask_irq() {
+  unsafe { asm!("msr daifclr, #2") };
+}
+
+/// Mask FIQ's at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn mask_fiq() {
+  unsafe { asm!("msr daifset, #1") };
+}
+
+/// Unmask FIQ's at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn unmask_fiq() {
+  unsafe { asm!("msr daifclr, #1") };
+}
+
+/// Mask SError interrupts at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn mask_serror() {
+  unsafe { asm!("msr daifset, #4") };
+}
+
+/// Unmask SError interrupts at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn unmask_serror() {
+  unsafe { asm!("msr daifclr, #4") };
+}
+
+/// Check whether IRQ's are masked at the current exception level
+#[inline]
+#[allow(dead_code)]
+pub fn irq_masked() -> bool {
+  daif::read(daif::I::Field) == daif::I::MASKED
+}
+
+/// Guard that keeps IRQ and FIQ masked as long as it is alive. Dropping the guard restores the interrupt mask state
+/// that was active when the guard has been acquired. This allows nesting of guards.
+#[must_use]
+pub struct InterruptGuard {
+  daif: u64,
+}
+
+impl InterruptGuard {
+  /// Mask IRQ and FIQ at the current exception level and remember the previous state
+  #[inline]
+  pub fn acquire() -> Self {
+    let daif = daif::get();
+    unsafe { asm!("msr daifset, #3") };
+    Self { daif }
+  }
+}
+
+impl Drop for InterruptGuard {
+  #[inline]
+  fn drop(&mut self) {
+    daif::set(self.daif);
+  }
+}
+
+#[cfg(feature = "critical-section-single-core")]
+mod single_core {
+  use super::*;
+  use core::mem::ManuallyDrop;
+
+  struct SingleCoreCriticalSection;
+  critical_section::set_impl!(SingleCoreCriticalSection);
+
+  unsafe impl critical_section::Impl for SingleCoreCriticalSection {
+    unsafe fn acquire() -> critical_section::RawRestoreState {
+      // the guard is released explicitly in ``release``, so do not run it's drop here
+      let guard = ManuallyDrop::new(InterruptGuard::acquire());
+      guard.daif
+    }
+
+    unsafe fn release(daif: critical_section::RawRestoreState) {
+      drop(InterruptGuard { daif });
+    }
+  }
+}
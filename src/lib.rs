@@ -6,7 +6,9 @@
  **********************************************************************************************************************/
 #![doc(html_root_url = "https://docs.rs/ruspiro-arch-aarch64/||VERSION||")]
 // we require to run with 'std' in unit tests and doc tests to have an allocator in place
-#![no_std]
+#![cfg_attr(not(test), no_std)]
+#![feature(asm_const)]
+#![feature(const_fn_trait_bound)]
 
 //! # RusPiRo Aarch64 specific API
 //!
@@ -15,5 +17,19 @@
 
 pub use ruspiro_register::*;
 
+pub mod addr;
+pub mod cache;
+pub mod context;
+pub mod debug;
+pub mod exception;
+pub mod fp;
 pub mod instructions;
+pub mod interrupt;
+pub mod mmu;
+pub mod percpu;
 pub mod register;
+pub mod smccc;
+pub mod spintable;
+pub mod syscall;
+pub mod uaccess;
+pub mod virt;
@@ -0,0 +1,395 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # ASID and VMID Allocation
+//!
+//! Address space identifiers (ASID) tag the TLB entries of the stage 1 translation of a process, virtual machine
+//! identifiers (VMID) tag the TLB entries of a guest. With only 256 or 65536 identifiers available they need to be
+//! recycled. The allocators use a generation scheme: each address space stores its identifier together with the
+//! generation it was allocated in. If all identifiers of the current generation are used up the generation is bumped,
+//! the address spaces get new identifiers when they are switched to the next time. The identifiers that are active on
+//! any core at the time of the rollover are kept reserved for them.
+//!
+//! Instead of a broadcast TLB invalidation at the rollover each core gets a pending flush. As the rollover resets the
+//! active identifiers of all cores, each of them takes the slow path on its next ``switch`` and invalidates its local
+//! TLB after the new identifier is assigned, before the caller activates the address space. This covers TLB entries
+//! that are created speculatively on a core with its old identifier while the rollover is in progress.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::mmu::asid::*;
+//! # use ruspiro_arch_aarch64::register::el1::{mpidr_el1::{Affinity, Topology}, ttbr0_el1};
+//! # use core::sync::atomic::AtomicU64;
+//! static ASIDS: AsidAllocator<4> = AsidAllocator::new(IdWidth::Bits8, FlushEl1);
+//!
+//! struct Process {
+//!   context_id: AtomicU64,
+//!   table: u64,
+//! }
+//!
+//! fn switch_to(process: &Process) {
+//!   let core = Affinity::current().to_linear_index(Topology::new(4, 1));
+//!   let asid = ASIDS.switch(core, &process.context_id);
+//!   ttbr0_el1::set(process.table | ((asid as u64) << 48));
+//! }
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::instructions::isb;
+#[cfg(target_arch = "aarch64")]
+use crate::register::el1::{id_aa64mmfr0_el1, id_aa64mmfr1_el1, tcr_el1};
+#[cfg(target_arch = "aarch64")]
+use crate::register::el2::vtcr_el2;
+#[cfg(target_arch = "aarch64")]
+use core::arch::asm;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Number of ``u64`` words of the bitmap of used identifiers, large enough for 16Bit identifiers
+const MAP_WORDS: usize = (1 << 16) / 64;
+
+/// Initial value of the identifier active on a core
+#[allow(clippy::declare_interior_mutable_const)]
+const INACTIVE: AtomicU64 = AtomicU64::new(0);
+
+/// Invalidates TLB entries when the identifiers are recycled
+pub trait TlbFlush {
+  /// Invalidate all TLB entries tagged with any identifier of the allocator on the current core
+  fn flush_local(&self);
+}
+
+/// Invalidate all stage 1 TLB entries of the EL1&0 translation regime of the current VMID on the current core
+#[cfg(target_arch = "aarch64")]
+pub struct FlushEl1;
+
+#[cfg(target_arch = "aarch64")]
+impl TlbFlush for FlushEl1 {
+  fn flush_local(&self) {
+    super::tlb::invalidate_all_el1_local();
+  }
+}
+
+/// Invalidate all stage 1 and stage 2 TLB entries of the EL1&0 translation regime of all VMIDs on the current core.
+/// This requires to be executed at EL2
+#[cfg(target_arch = "aarch64")]
+pub struct FlushAllGuests;
+
+#[cfg(target_arch = "aarch64")]
+impl TlbFlush for FlushAllGuests {
+  fn flush_local(&self) {
+    unsafe { asm!("dsb nshst", "tlbi alle1", "dsb nsh") };
+    isb();
+  }
+}
+
+/// The width of the identifiers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IdWidth {
+  /// 8Bit identifiers
+  Bits8,
+  /// 16Bit identifiers
+  Bits16,
+}
+
+impl IdWidth {
+  /// The number of bits of an identifier
+  pub const fn bits(self) -> u32 {
+    match self {
+      IdWidth::Bits8 => 8,
+      IdWidth::Bits16 => 16,
+    }
+  }
+
+  /// The ASID width in use. 16Bit ASIDs require support by the core and ``TCR_EL1::AS`` being set
+  #[cfg(target_arch = "aarch64")]
+  pub fn asid() -> Self {
+    if id_aa64mmfr0_el1::read(id_aa64mmfr0_el1::ASIDBits::Field)
+      == id_aa64mmfr0_el1::ASIDBits::_16BITS
+      && tcr_el1::read(tcr_el1::AS::Field) == tcr_el1::AS::_16BITS
+    {
+      IdWidth::Bits16
+    } else {
+      IdWidth::Bits8
+    }
+  }
+
+  /// The VMID width in use. 16Bit VMIDs require support by the core and ``VTCR_EL2::VS`` being set
+  #[cfg(target_arch = "aarch64")]
+  pub fn vmid() -> Self {
+    if id_aa64mmfr1_el1::read(id_aa64mmfr1_el1::VMIDBits::Field)
+      == id_aa64mmfr1_el1::VMIDBits::_16BITS
+      && vtcr_el2::read(vtcr_el2::VS::Field) == vtcr_el2::VS::_16BIT
+    {
+      IdWidth::Bits16
+    } else {
+      IdWidth::Bits8
+    }
+  }
+}
+
+/// The state only modified while holding the allocator lock
+struct AllocatorState<const N: usize> {
+  map: [u64; MAP_WORDS],
+  next: u64,
+  reserved: [u64; N],
+  flush_pending: [bool; N],
+}
+
+/// Generation based identifier allocator shared by ``N`` cores. The identifier 0 is never handed out. The context id
+/// stored per address space holds the generation in the upper and the identifier in the lower bits, 0 represents an
+/// address space that has not been assigned an identifier yet.
+pub struct IdAllocator<const N: usize, F: TlbFlush> {
+  bits: u32,
+  generation: AtomicU64,
+  active: [AtomicU64; N],
+  lock: AtomicBool,
+  state: UnsafeCell<AllocatorState<N>>,
+  flush: F,
+}
+
+unsafe impl<const N: usize, F: TlbFlush + Sync> Sync for IdAllocator<N, F> {}
+
+impl<const N: usize, F: TlbFlush> IdAllocator<N, F> {
+  /// Create the allocator for identifiers of the given width
+  pub const fn new(width: IdWidth, flush: F) -> Self {
+    let mut map = [0; MAP_WORDS];
+    map[0] = 1;
+    Self {
+      bits: width.bits(),
+      generation: AtomicU64::new(1 << width.bits()),
+      active: [INACTIVE; N],
+      lock: AtomicBool::new(false),
+      state: UnsafeCell::new(AllocatorState {
+        map,
+        next: 1,
+        reserved: [0; N],
+        flush_pending: [false; N],
+      }),
+      flush,
+    }
+  }
+
+  /// Provide the identifier for the address space with the given context id that is about to be activated on the
+  /// given core. A new identifier is assigned and stored in the context id if the address space has none or its
+  /// identifier belongs to an outdated generation. The local TLB of the core is invalidated if a rollover happened
+  /// since its last switch, so this need to be called on the given core before the address space is activated.
+  pub fn switch(&self, core: usize, context_id: &AtomicU64) -> u16 {
+    let generation = self.generation.load(Ordering::Relaxed);
+    let id = context_id.load(Ordering::Relaxed);
+    // fast path: the identifier is still valid and no rollover is in progress, which resets all active identifiers
+    let old_active = self.active[core].load(Ordering::Relaxed);
+    if old_active != 0
+      && id != 0
+      && self.is_current(id, generation)
+      && self.active[core]
+        .compare_exchange(old_active, id, Ordering::Relaxed, Ordering::Relaxed)
+        .is_ok()
+    {
+      return self.identifier(id);
+    }
+
+    self.acquire();
+    let state = unsafe { &mut *self.state.get() };
+    let mut id = context_id.load(Ordering::Relaxed);
+    if id == 0 || !self.is_current(id, self.generation.load(Ordering::Relaxed)) {
+      id = self.new_id(state, id);
+      context_id.store(id, Ordering::Relaxed);
+    }
+    self.active[core].store(id, Ordering::Relaxed);
+    let flush_pending = core::mem::replace(&mut state.flush_pending[core], false);
+    self.release();
+    if flush_pending {
+      self.flush.flush_local();
+    }
+    self.identifier(id)
+  }
+
+  /// The generation of the identifiers handed out currently
+  pub fn generation(&self) -> u64 {
+    self.generation.load(Ordering::Relaxed) >> self.bits
+  }
+
+  fn new_id(&self, state: &mut AllocatorState<N>, id: u64) -> u64 {
+    let generation = self.generation.load(Ordering::Relaxed);
+    if id != 0 {
+      let identifier = id & self.mask();
+      // an identifier that was active during the rollover stays with its address space
+      let mut reserved = false;
+      for entry in state.reserved.iter_mut() {
+        if *entry == id {
+          *entry = generation | identifier;
+          reserved = true;
+        }
+      }
+      if reserved {
+        return generation | identifier;
+      }
+      // try to keep the identifier from the previous generation if it is not used yet
+      if !Self::test_and_set(&mut state.map, identifier) {
+        return generation | identifier;
+      }
+    }
+
+    let identifier = match self.find_free(&state.map, state.next) {
+      Some(identifier) => identifier,
+      None => {
+        self.rollover(state);
+        // at most N identifiers are reserved after the rollover
+        self.find_free(&state.map, 1).unwrap_or(0)
+      }
+    };
+    Self::test_and_set(&mut state.map, identifier);
+    state.next = identifier + 1;
+    self.generation.load(Ordering::Relaxed) | identifier
+  }
+
+  fn rollover(&self, state: &mut AllocatorState<N>) {
+    let generation = self.generation.load(Ordering::Relaxed) + (1 << self.bits);
+    self.generation.store(generation, Ordering::Relaxed);
+    state.map = [0; MAP_WORDS];
+    state.map[0] = 1;
+    for (active, reserved) in self.active.iter().zip(state.reserved.iter_mut()) {
+      // a core that has not switched since the last rollover still runs with its reserved identifier
+      let id = active.swap(0, Ordering::Relaxed);
+      if id != 0 {
+        *reserved = id;
+      }
+      Self::test_and_set(&mut state.map, *reserved & self.mask());
+    }
+    state.next = 1;
+    state.flush_pending = [true; N];
+  }
+
+  fn find_free(&self, map: &[u64; MAP_WORDS], from: u64) -> Option<u64> {
+    (from..=self.mask())
+      .find(|&identifier| map[(identifier / 64) as usize] & (1 << (identifier % 64)) == 0)
+  }
+
+  fn test_and_set(map: &mut [u64; MAP_WORDS], identifier: u64) -> bool {
+    let word = &mut map[(identifier / 64) as usize];
+    let bit = 1 << (identifier % 64);
+    let was_set = *word & bit != 0;
+    *word |= bit;
+    was_set
+  }
+
+  fn is_current(&self, id: u64, generation: u64) -> bool {
+    (id ^ generation) >> self.bits == 0
+  }
+
+  fn identifier(&self, id: u64) -> u16 {
+    (id & self.mask()) as u16
+  }
+
+  fn mask(&self) -> u64 {
+    (1 << self.bits) - 1
+  }
+
+  fn acquire(&self) {
+    while self
+      .lock
+      .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+      .is_err()
+    {
+      core::hint::spin_loop();
+    }
+  }
+
+  fn release(&self) {
+    self.lock.store(false, Ordering::Release);
+  }
+}
+
+/// Allocator of address space identifiers shared by ``N`` cores
+#[cfg(target_arch = "aarch64")]
+pub type AsidAllocator<const N: usize, F = FlushEl1> = IdAllocator<N, F>;
+
+/// Allocator of virtual machine identifiers shared by ``N`` cores
+#[cfg(target_arch = "aarch64")]
+pub type VmidAllocator<const N: usize, F = FlushAllGuests> = IdAllocator<N, F>;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use core::cell::Cell;
+
+  /// Counts the TLB flushes instead of executing them
+  #[derive(Default)]
+  struct FakeFlush {
+    count: Cell<usize>,
+  }
+
+  impl TlbFlush for &FakeFlush {
+    fn flush_local(&self) {
+      self.count.set(self.count.get() + 1);
+    }
+  }
+
+  #[test]
+  fn identifier_0_is_never_handed_out() {
+    let flush = FakeFlush::default();
+    let allocator: IdAllocator<1, _> = IdAllocator::new(IdWidth::Bits8, &flush);
+    for _ in 0..3 * 256 {
+      let context_id = AtomicU64::new(0);
+      assert_ne!(allocator.switch(0, &context_id), 0);
+    }
+    assert_eq!(flush.count.get(), 3);
+  }
+
+  #[test]
+  fn rollover_starts_a_new_generation() {
+    let flush = FakeFlush::default();
+    let allocator: IdAllocator<1, _> = IdAllocator::new(IdWidth::Bits8, &flush);
+    let first = AtomicU64::new(0);
+    assert_eq!(allocator.switch(0, &first), 1);
+    for identifier in 2..=255 {
+      assert_eq!(allocator.switch(0, &AtomicU64::new(0)), identifier);
+    }
+    assert_eq!(flush.count.get(), 0);
+    assert_eq!(allocator.generation(), 1);
+
+    // all identifiers are used up, the next one triggers the rollover
+    assert_eq!(allocator.switch(0, &AtomicU64::new(0)), 1);
+    assert_eq!(flush.count.get(), 1);
+    assert_eq!(allocator.generation(), 2);
+    // the identifier of the outdated generation is already taken, so a new one is assigned
+    assert_eq!(allocator.switch(0, &first), 2);
+    assert_eq!(first.load(Ordering::Relaxed), 2 << 8 | 2);
+  }
+
+  #[test]
+  fn active_identifiers_are_reserved_over_rollover() {
+    let flush = FakeFlush::default();
+    let allocator: IdAllocator<2, _> = IdAllocator::new(IdWidth::Bits8, &flush);
+    let running = AtomicU64::new(0);
+    let reserved = allocator.switch(1, &running);
+    while flush.count.get() == 0 {
+      allocator.switch(0, &AtomicU64::new(0));
+    }
+    // until the next rollover no other address space gets the identifier reserved for core 1
+    for _ in 0..250 {
+      assert_ne!(allocator.switch(0, &AtomicU64::new(0)), reserved);
+    }
+    assert_eq!(flush.count.get(), 1);
+    // core 1 flushes its pending TLB invalidation with its next switch
+    assert_eq!(allocator.switch(1, &running), reserved);
+    assert_eq!(running.load(Ordering::Relaxed) >> 8, 2);
+    assert_eq!(flush.count.get(), 2);
+    allocator.switch(1, &running);
+    assert_eq!(flush.count.get(), 2);
+  }
+
+  #[test]
+  fn valid_identifier_is_kept() {
+    let flush = FakeFlush::default();
+    let allocator: IdAllocator<2, _> = IdAllocator::new(IdWidth::Bits16, &flush);
+    let context_id = AtomicU64::new(0);
+    let identifier = allocator.switch(0, &context_id);
+    assert_eq!(allocator.switch(1, &context_id), identifier);
+    assert_eq!(allocator.switch(0, &context_id), identifier);
+    assert_eq!(flush.count.get(), 0);
+  }
+}
@@ -0,0 +1,201 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Address Translation
+//!
+//! Translate virtual addresses with the address translation (AT) instructions. The translation is performed by the
+//! hardware using the current translation tables of the requested translation regime and reports the result in
+//! ``PAR_EL1``.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::VirtAddr;
+//! # use ruspiro_arch_aarch64::mmu::at::*;
+//! // check whether the guest may write to a buffer it passed to the hypervisor
+//! # let guest_va = VirtAddr::new(0x8_0000);
+//! match translate(guest_va, Regime::Stage12El1, Access::Write) {
+//!   Ok(translation) => { /* access the buffer at translation.pa */ }
+//!   Err(fault) if fault.stage2 => { /* the IPA is not mapped for the guest */ }
+//!   Err(_) => { /* the guest passed an invalid address */ }
+//! }
+//! ```
+//!
+//! As ``PAR_EL1`` is not preserved across exceptions, an exception handler using address translation instructions may
+//! corrupt the result of an interrupted translation.
+
+use super::Shareability;
+use crate::addr::{Granule, PhysAddr, VirtAddr};
+#[cfg(target_arch = "aarch64")]
+use crate::instructions::isb;
+use crate::register::el1::par_el1;
+#[cfg(target_arch = "aarch64")]
+use core::arch::asm;
+
+/// The translation regime and stages used for the address translation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Regime {
+  /// Stage 1 of the EL1&0 translation regime with the permissions of EL1
+  El1,
+  /// Stage 1 of the EL1&0 translation regime with the permissions of EL1, taking ``PSTATE.PAN`` into account
+  El1Pan,
+  /// Stage 1 of the EL1&0 translation regime with the permissions of EL0
+  El0,
+  /// Stage 1 of the EL2 translation regime, requires to run at EL2 or EL3
+  El2,
+  /// Stage 1 and 2 of the EL1&0 translation regime with the permissions of EL1, requires to run at EL2 or EL3
+  Stage12El1,
+  /// Stage 1 and 2 of the EL1&0 translation regime with the permissions of EL0, requires to run at EL2 or EL3
+  Stage12El0,
+}
+
+/// The access the permissions are checked for
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+  Read,
+  Write,
+}
+
+/// The result of a successful address translation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Translation {
+  /// The physical address the virtual address translates to
+  pub pa: PhysAddr,
+  /// The memory attributes in the ``MAIR_ELx`` encoding
+  pub attr: u8,
+  /// The shareability of the memory
+  pub shareability: Shareability,
+  /// The output address is in the Non-secure address space
+  pub non_secure: bool,
+}
+
+/// The kind of fault reported by the fault status code
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FaultKind {
+  /// Address size fault at the given level
+  AddressSize(u8),
+  /// Translation fault at the given level
+  Translation(u8),
+  /// Access flag fault at the given level
+  AccessFlag(u8),
+  /// Permission fault at the given level
+  Permission(u8),
+  /// Synchronous external abort, not on a translation table walk
+  ExternalAbort,
+  /// Synchronous external abort on the translation table walk at the given level
+  ExternalAbortOnWalk(u8),
+  /// TLB conflict abort
+  TlbConflict,
+  /// Any other fault status code
+  Other(u8),
+}
+
+/// The fault information of an aborted address translation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TranslationFault {
+  /// The fault status code
+  pub status: u8,
+  /// The fault was caused by the stage 2 translation
+  pub stage2: bool,
+  /// The fault was caused by the stage 2 translation of a stage 1 translation table walk
+  pub table_walk: bool,
+}
+
+impl TranslationFault {
+  /// Decode the fault status code
+  pub const fn kind(&self) -> FaultKind {
+    let level = self.status & 0b11;
+    match self.status >> 2 {
+      0b0000 => FaultKind::AddressSize(level),
+      0b0001 => FaultKind::Translation(level),
+      0b0010 => FaultKind::AccessFlag(level),
+      0b0011 => FaultKind::Permission(level),
+      0b0101 => FaultKind::ExternalAbortOnWalk(level),
+      _ => match self.status {
+        0b01_0000 => FaultKind::ExternalAbort,
+        0b11_0000 => FaultKind::TlbConflict,
+        _ => FaultKind::Other(self.status),
+      },
+    }
+  }
+}
+
+/// Decode a raw ``PAR_EL1`` value of the translation of the given virtual address
+pub fn decode_par(va: VirtAddr, par: u64) -> Result<Translation, TranslationFault> {
+  let field = |field: crate::RegisterField<u64>| (par & field.mask()) >> field.shift();
+  if field(par_el1::F::Field) != 0 {
+    return Err(TranslationFault {
+      status: field(par_el1::FST::Field) as u8,
+      stage2: par & par_el1::S::Field.mask() == par_el1::S::STAGE2.raw_value(),
+      table_walk: field(par_el1::PTW::Field) != 0,
+    });
+  }
+  Ok(Translation {
+    pa: PhysAddr::new(par & par_el1::PA::Field.mask()) + va.page_offset(Granule::_4KB),
+    attr: field(par_el1::ATTR::Field) as u8,
+    shareability: match field(par_el1::SH::Field) {
+      0b10 => Shareability::OuterShareable,
+      0b11 => Shareability::InnerShareable,
+      _ => Shareability::NonShareable,
+    },
+    non_secure: field(par_el1::NS::Field) != 0,
+  })
+}
+
+/// Translate the virtual address in the given translation regime, checking the permissions for the given access
+#[cfg(target_arch = "aarch64")]
+pub fn translate(
+  va: VirtAddr,
+  regime: Regime,
+  access: Access,
+) -> Result<Translation, TranslationFault> {
+  unsafe {
+    match (regime, access) {
+      (Regime::El1, Access::Read) => asm!("at s1e1r, {0}", in(reg) va.as_u64()),
+      (Regime::El1, Access::Write) => asm!("at s1e1w, {0}", in(reg) va.as_u64()),
+      // AT S1E1RP and AT S1E1WP using their SYS instruction encoding
+      (Regime::El1Pan, Access::Read) => asm!("sys #0, c7, c9, #0, {0}", in(reg) va.as_u64()),
+      (Regime::El1Pan, Access::Write) => asm!("sys #0, c7, c9, #1, {0}", in(reg) va.as_u64()),
+      (Regime::El0, Access::Read) => asm!("at s1e0r, {0}", in(reg) va.as_u64()),
+      (Regime::El0, Access::Write) => asm!("at s1e0w, {0}", in(reg) va.as_u64()),
+      (Regime::El2, Access::Read) => asm!("at s1e2r, {0}", in(reg) va.as_u64()),
+      (Regime::El2, Access::Write) => asm!("at s1e2w, {0}", in(reg) va.as_u64()),
+      (Regime::Stage12El1, Access::Read) => asm!("at s12e1r, {0}", in(reg) va.as_u64()),
+      (Regime::Stage12El1, Access::Write) => asm!("at s12e1w, {0}", in(reg) va.as_u64()),
+      (Regime::Stage12El0, Access::Read) => asm!("at s12e0r, {0}", in(reg) va.as_u64()),
+      (Regime::Stage12El0, Access::Write) => asm!("at s12e0w, {0}", in(reg) va.as_u64()),
+    }
+  }
+  // the result in PAR_EL1 is only guaranteed to be visible after a context synchronization
+  isb();
+  decode_par(va, par_el1::get())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn decode_successful_translation() {
+    let translation = decode_par(VirtAddr::new(0x4000_1234), 0xFF00_0000_3F20_0180).unwrap();
+    assert_eq!(translation.pa, PhysAddr::new(0x3F20_0234));
+    assert_eq!(translation.attr, 0xFF);
+    assert_eq!(translation.shareability, Shareability::InnerShareable);
+    assert!(!translation.non_secure);
+  }
+
+  #[test]
+  fn decode_stage2_fault() {
+    // translation fault level 3 on the stage 2 walk of a stage 1 table
+    let fault = decode_par(VirtAddr::new(0x4000_1234), 0x30F).unwrap_err();
+    assert_eq!(fault.kind(), FaultKind::Translation(3));
+    assert!(fault.stage2);
+    assert!(fault.table_walk);
+    // permission fault level 2 of the stage 1 translation
+    let fault = decode_par(VirtAddr::new(0x4000_1234), 0x01D).unwrap_err();
+    assert_eq!(fault.kind(), FaultKind::Permission(2));
+    assert!(!fault.stage2);
+  }
+}
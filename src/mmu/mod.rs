@@ -0,0 +1,121 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Translation Tables
+//!
+//! The parts of the VMSAv8-64 translation table format that are shared between the stage 1 and the stage 2
+//! translation. All definitions are based on the 4KB translation granule.
+//!
+
+pub mod asid;
+pub mod at;
+pub mod stage2;
+pub mod tlb;
+
+use crate::addr::{InputAddress, PhysAddr};
+
+/// Size of the translation granule and thus of a single translation table in bytes
+pub const GRANULE_SIZE: usize = 4096;
+
+/// Number of descriptors within a single translation table
+pub const ENTRIES_PER_TABLE: usize = 512;
+
+/// Number of address bits resolved by each level of the translation table walk
+pub const BITS_PER_LEVEL: u32 = 9;
+
+/// Descriptor bit marking the descriptor as valid
+pub const DESC_VALID: u64 = 1 << 0;
+
+/// Descriptor bit marking a level 0 to 2 descriptor as table descriptor and a level 3 descriptor as page descriptor
+pub const DESC_TABLE: u64 = 1 << 1;
+
+/// Access flag of a block or page descriptor
+pub const DESC_AF: u64 = 1 << 10;
+
+/// Mask of the output address bits\[47:12\] of a descriptor
+pub const OUTPUT_ADDRESS_MASK: u64 = 0x0000_FFFF_FFFF_F000;
+
+/// Bit position of the shareability attribute within a block or page descriptor
+const SH_SHIFT: u32 = 8;
+
+/// The shareability of the memory region mapped by a block or page descriptor
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u64)]
+pub enum Shareability {
+  NonShareable = 0b00,
+  OuterShareable = 0b10,
+  InnerShareable = 0b11,
+}
+
+impl Shareability {
+  /// The shareability encoded into the descriptor bits\[9:8\]
+  pub const fn to_descriptor(self) -> u64 {
+    (self as u64) << SH_SHIFT
+  }
+}
+
+/// The bit position of the lowest address bit resolved at the given level of the translation table walk
+pub const fn level_shift(level: u8) -> u32 {
+  12 + BITS_PER_LEVEL * (3 - level as u32)
+}
+
+/// The size of the memory region covered by a single descriptor at the given level
+pub const fn level_size(level: u8) -> u64 {
+  1 << level_shift(level)
+}
+
+/// The index of the descriptor covering the given input address within a translation table at the given level. The
+/// input address is the virtual address of a stage 1 and the intermediate physical address of a stage 2 translation.
+pub fn table_index<A: InputAddress>(addr: A, level: u8) -> usize {
+  ((addr.as_u64() >> level_shift(level)) as usize) & (ENTRIES_PER_TABLE - 1)
+}
+
+/// A single translation table of the 4KB granule
+#[derive(Copy, Clone)]
+#[repr(C, align(4096))]
+pub struct TranslationTable {
+  pub entries: [u64; ENTRIES_PER_TABLE],
+}
+
+impl TranslationTable {
+  /// Create a translation table where all descriptors are invalid
+  pub const fn new() -> Self {
+    Self {
+      entries: [0; ENTRIES_PER_TABLE],
+    }
+  }
+
+  /// The physical address of this table, assuming it is identity mapped
+  pub fn base_address(&self) -> PhysAddr {
+    PhysAddr::new(self as *const Self as u64)
+  }
+}
+
+impl Default for TranslationTable {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::addr::{IntermediatePhysAddr, VirtAddr};
+
+  #[test]
+  fn table_index_of_each_level() {
+    let va = VirtAddr::new(0x1A << 39 | 0x1B << 30 | 0x1C << 21 | 0x1D << 12 | 0x123);
+    assert_eq!(table_index(va, 0), 0x1A);
+    assert_eq!(table_index(va, 1), 0x1B);
+    assert_eq!(table_index(va, 2), 0x1C);
+    assert_eq!(table_index(va, 3), 0x1D);
+    assert_eq!(
+      table_index(IntermediatePhysAddr::new(0x3F20_0000), 2),
+      0x1F9
+    );
+  }
+}
@@ -0,0 +1,306 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Stage 2 Translation
+//!
+//! Descriptor builder and table layout for the stage 2 translation of intermediate physical addresses (IPA) of a
+//! virtual machine into physical addresses. The initial lookup level of a stage 2 walk may consist of up to 16
+//! concatenated translation tables. The ``Stage2Layout`` selects the starting level with the fewest lookups for a given
+//! IPA size and provides the matching ``VTCR_EL2`` settings.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::PhysAddr;
+//! # use ruspiro_arch_aarch64::mmu::{TranslationTable, stage2::*};
+//! # use ruspiro_arch_aarch64::register::el2::{vtcr_el2, vttbr_el2};
+//! // a 40 bit IPA space starts the walk at level 1 with two concatenated tables
+//! #[repr(C, align(8192))]
+//! struct Root([TranslationTable; 2]);
+//! static mut ROOT: Root = Root([TranslationTable::new(); 2]);
+//!
+//! let layout = Stage2Layout::new(40).unwrap();
+//! unsafe {
+//!   ROOT.0[0].entries[0] = Stage2Descriptor::block(PhysAddr::new(0x0), 1)
+//!     .unwrap()
+//!     .with_mem_attr(S2MemAttr::NormalWriteBack)
+//!     .raw();
+//!   vtcr_el2::write(layout.vtcr() | vtcr_el2::PS::_40BITS | vtcr_el2::RES1::with_value(1));
+//!   vttbr_el2::write(vttbr_el2::VMID::with_value(1));
+//!   // the root tables are identity mapped
+//!   vttbr_el2::set_table_base(PhysAddr::new(ROOT.0.as_ptr() as u64));
+//! }
+//! ```
+
+use super::*;
+use crate::addr::{IntermediatePhysAddr, PhysAddr};
+use crate::register::el2::vtcr_el2;
+use crate::RegisterFieldValue;
+
+/// Bit position of the stage 2 memory attributes within a block or page descriptor
+const MEMATTR_SHIFT: u32 = 2;
+/// Bit position of the stage 2 access permissions within a block or page descriptor
+const S2AP_SHIFT: u32 = 6;
+/// Execute never bit of a stage 2 block or page descriptor
+const XN: u64 = 1 << 54;
+/// Mask of the stage 2 attributes of a block or page descriptor
+const ATTR_MASK: u64 = (0b1111 << MEMATTR_SHIFT) | (0b11 << S2AP_SHIFT) | (0b11 << 8) | XN;
+
+/// Maximum number of concatenated translation tables at the initial lookup level
+pub const MAX_CONCATENATED_TABLES: usize = 16;
+
+/// Stage 2 data access permissions (``S2AP``) of a block or page
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u64)]
+pub enum S2Access {
+  None = 0b00,
+  ReadOnly = 0b01,
+  WriteOnly = 0b10,
+  ReadWrite = 0b11,
+}
+
+/// Stage 2 memory attributes (``MemAttr``) of a block or page. Unlike stage 1 they are encoded directly in the
+/// descriptor and not as an index into a MAIR register
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u64)]
+pub enum S2MemAttr {
+  DeviceNGnRnE = 0b0000,
+  DeviceNGnRE = 0b0001,
+  DeviceNGRE = 0b0010,
+  DeviceGRE = 0b0011,
+  /// Normal memory, outer and inner non-cacheable
+  NormalNonCacheable = 0b0101,
+  /// Normal memory, outer and inner write-through cacheable
+  NormalWriteThrough = 0b1010,
+  /// Normal memory, outer and inner write-back cacheable
+  NormalWriteBack = 0b1111,
+}
+
+/// A stage 2 translation table descriptor
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Stage2Descriptor(u64);
+
+impl Stage2Descriptor {
+  /// A descriptor faulting any access to the region it covers
+  pub const fn invalid() -> Self {
+    Self(0)
+  }
+
+  /// Wrap the raw descriptor value
+  pub const fn from_raw(raw: u64) -> Self {
+    Self(raw)
+  }
+
+  /// A table descriptor pointing to the translation table of the next level. Valid at the levels 0 to 2
+  pub const fn table(next_level: PhysAddr) -> Self {
+    Self((next_level.as_u64() & OUTPUT_ADDRESS_MASK) | DESC_TABLE | DESC_VALID)
+  }
+
+  /// A block descriptor mapping the naturally aligned region of ``level_size(level)`` starting at ``output``.
+  /// Blocks are only supported at the levels 1 and 2, ``None`` is returned for any other level or a misaligned
+  /// output address. The block is mapped as normal write-back, inner shareable, read-write and executable memory
+  pub const fn block(output: PhysAddr, level: u8) -> Option<Self> {
+    if level < 1 || level > 2 || !output.is_aligned(level_size(level)) {
+      return None;
+    }
+    Some(Self::leaf(output))
+  }
+
+  /// A level 3 page descriptor mapping the 4KB page at ``output``. The page is mapped as normal write-back, inner
+  /// shareable, read-write and executable memory
+  pub const fn page(output: PhysAddr) -> Self {
+    Self(Self::leaf(output).0 | DESC_TABLE)
+  }
+
+  const fn leaf(output: PhysAddr) -> Self {
+    Self(
+      (output.as_u64() & OUTPUT_ADDRESS_MASK)
+        | DESC_AF
+        | Shareability::InnerShareable.to_descriptor()
+        | ((S2Access::ReadWrite as u64) << S2AP_SHIFT)
+        | ((S2MemAttr::NormalWriteBack as u64) << MEMATTR_SHIFT)
+        | DESC_VALID,
+    )
+  }
+
+  /// Replace the access permissions of a block or page descriptor
+  pub const fn with_access(self, access: S2Access) -> Self {
+    Self((self.0 & !(0b11 << S2AP_SHIFT)) | ((access as u64) << S2AP_SHIFT))
+  }
+
+  /// Replace the memory attributes of a block or page descriptor
+  pub const fn with_mem_attr(self, attr: S2MemAttr) -> Self {
+    Self((self.0 & !(0b1111 << MEMATTR_SHIFT)) | ((attr as u64) << MEMATTR_SHIFT))
+  }
+
+  /// Replace the shareability of a block or page descriptor
+  pub const fn with_shareability(self, shareability: Shareability) -> Self {
+    Self((self.0 & !(0b11 << 8)) | shareability.to_descriptor())
+  }
+
+  /// Set or clear the execute never bit of a block or page descriptor
+  pub const fn with_execute_never(self, xn: bool) -> Self {
+    if xn {
+      Self(self.0 | XN)
+    } else {
+      Self(self.0 & !XN)
+    }
+  }
+
+  /// The raw descriptor value to be stored in the translation table
+  pub const fn raw(self) -> u64 {
+    self.0
+  }
+
+  /// Whether the descriptor is valid
+  pub const fn is_valid(self) -> bool {
+    self.0 & DESC_VALID != 0
+  }
+
+  /// The output address of a block or page descriptor or the next level table address of a table descriptor
+  pub const fn output_address(self) -> PhysAddr {
+    PhysAddr::new(self.0 & OUTPUT_ADDRESS_MASK)
+  }
+
+  /// The stage 2 attributes of a block or page descriptor
+  pub const fn attributes(self) -> u64 {
+    self.0 & ATTR_MASK
+  }
+}
+
+/// The layout of the stage 2 translation tables for a given IPA size
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stage2Layout {
+  ipa_bits: u8,
+  start_level: u8,
+  concatenated: u8,
+}
+
+impl Stage2Layout {
+  /// Select the starting level for an IPA size between 25 and 48 bits. The deepest possible starting level is chosen,
+  /// concatenating up to 16 tables at the initial lookup level, to keep the number of lookups of a translation table
+  /// walk low. Level 0 is only used for IPA sizes that can not be covered by concatenated level 1 tables.
+  pub const fn new(ipa_bits: u8) -> Option<Self> {
+    if ipa_bits < 25 || ipa_bits > 48 {
+      return None;
+    }
+    let mut level = 2;
+    while level > 0 {
+      let top = level_shift(level) + BITS_PER_LEVEL;
+      if ipa_bits as u32 <= top + 4 {
+        let excess = (ipa_bits as u32).saturating_sub(top);
+        return Some(Self {
+          ipa_bits,
+          start_level: level,
+          concatenated: 1 << excess,
+        });
+      }
+      level -= 1;
+    }
+    Some(Self {
+      ipa_bits,
+      start_level: 0,
+      concatenated: 1,
+    })
+  }
+
+  /// The size of the IPA space in bits
+  pub const fn ipa_bits(&self) -> u8 {
+    self.ipa_bits
+  }
+
+  /// The level the translation table walk starts at
+  pub const fn start_level(&self) -> u8 {
+    self.start_level
+  }
+
+  /// The number of concatenated tables at the initial lookup level. The root tables need to be contiguous in memory
+  /// and aligned to their overall size
+  pub const fn concatenated_tables(&self) -> usize {
+    self.concatenated as usize
+  }
+
+  /// The size in bytes of the concatenated root tables
+  pub const fn root_size(&self) -> usize {
+    self.concatenated as usize * GRANULE_SIZE
+  }
+
+  /// The value for ``VTCR_EL2::T0SZ``
+  pub const fn t0sz(&self) -> u64 {
+    64 - self.ipa_bits as u64
+  }
+
+  /// The value for ``VTCR_EL2::SL0`` for the 4KB granule
+  pub const fn sl0(&self) -> u64 {
+    2 - self.start_level as u64
+  }
+
+  /// The index of the descriptor covering the IPA within the concatenated root tables
+  pub const fn root_index(&self, ipa: IntermediatePhysAddr) -> usize {
+    let entries = ENTRIES_PER_TABLE * self.concatenated as usize;
+    ((ipa.as_u64() >> level_shift(self.start_level)) as usize) & (entries - 1)
+  }
+
+  /// The ``VTCR_EL2`` fields describing this layout with the 4KB granule. Cacheability, shareability and the physical
+  /// address size need to be added by the caller
+  pub fn vtcr(&self) -> RegisterFieldValue<u64> {
+    vtcr_el2::T0SZ::with_value(self.t0sz())
+      | vtcr_el2::SL0::with_value(self.sl0())
+      | vtcr_el2::TG0::_4KB
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ipa_size_limits() {
+    assert_eq!(Stage2Layout::new(24), None);
+    assert_eq!(Stage2Layout::new(49), None);
+  }
+
+  #[test]
+  fn start_level_2_with_concatenated_tables() {
+    let layout = Stage2Layout::new(30).unwrap();
+    assert_eq!((layout.start_level(), layout.concatenated_tables()), (2, 1));
+    assert_eq!(layout.sl0(), 0);
+    let layout = Stage2Layout::new(34).unwrap();
+    assert_eq!(
+      (layout.start_level(), layout.concatenated_tables()),
+      (2, 16)
+    );
+    assert_eq!(layout.root_size(), 16 * GRANULE_SIZE);
+  }
+
+  #[test]
+  fn start_level_1_with_concatenated_tables() {
+    let layout = Stage2Layout::new(35).unwrap();
+    assert_eq!((layout.start_level(), layout.concatenated_tables()), (1, 1));
+    let layout = Stage2Layout::new(40).unwrap();
+    assert_eq!((layout.start_level(), layout.concatenated_tables()), (1, 2));
+    assert_eq!(layout.sl0(), 1);
+    assert_eq!(layout.t0sz(), 24);
+    assert_eq!(
+      layout.root_index(IntermediatePhysAddr::new(0xC0_4000_0000)),
+      0x301
+    );
+    let layout = Stage2Layout::new(43).unwrap();
+    assert_eq!(
+      (layout.start_level(), layout.concatenated_tables()),
+      (1, 16)
+    );
+  }
+
+  #[test]
+  fn start_level_0_without_concatenation() {
+    for ipa_bits in [44, 48] {
+      let layout = Stage2Layout::new(ipa_bits).unwrap();
+      assert_eq!((layout.start_level(), layout.concatenated_tables()), (0, 1));
+      assert_eq!(layout.sl0(), 2);
+    }
+  }
+}
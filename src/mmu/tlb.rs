@@ -0,0 +1,78 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TLB Maintenance
+//!
+//! Invalidate TLB entries in the inner shareable domain after translation table descriptors have been changed. The
+//! descriptor updates are made visible to the table walk before the invalidation and the invalidation is completed
+//! before returning.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::VirtAddr;
+//! # use ruspiro_arch_aarch64::mmu::tlb;
+//! // a page descriptor of the process with ASID 5 was changed
+//! tlb::invalidate_va(VirtAddr::new(0x40_0000), 5);
+//! ```
+#![cfg(target_arch = "aarch64")]
+
+use crate::addr::{IntermediatePhysAddr, VirtAddr};
+use crate::instructions::isb;
+use core::arch::asm;
+
+/// The operand of the TLBI by address instructions holding the page number of a 4KB page
+const fn page_number(addr: u64) -> u64 {
+  (addr >> 12) & 0x0FFF_FFFF_FFFF
+}
+
+/// Invalidate the stage 1 TLB entries of the EL1&0 translation regime for the virtual address and ASID
+#[inline]
+pub fn invalidate_va(va: VirtAddr, asid: u16) {
+  let operand = page_number(va.as_u64()) | ((asid as u64) << 48);
+  unsafe { asm!("dsb ishst", "tlbi vae1is, {0}", "dsb ish", in(reg) operand) };
+  isb();
+}
+
+/// Invalidate the stage 1 TLB entries of the EL1&0 translation regime for the virtual address and any ASID
+#[inline]
+pub fn invalidate_va_all_asids(va: VirtAddr) {
+  let operand = page_number(va.as_u64());
+  unsafe { asm!("dsb ishst", "tlbi vaae1is, {0}", "dsb ish", in(reg) operand) };
+  isb();
+}
+
+/// Invalidate the stage 2 TLB entries for the intermediate physical address of the current VMID. As combined stage 1
+/// and stage 2 entries may still hold the old translation, all stage 1 entries of the current VMID are invalidated as
+/// well. This requires to be executed at EL2
+#[inline]
+pub fn invalidate_ipa(ipa: IntermediatePhysAddr) {
+  let operand = page_number(ipa.as_u64());
+  unsafe {
+    asm!(
+      "dsb ishst",
+      "tlbi ipas2e1is, {0}",
+      "dsb ish",
+      "tlbi vmalle1is",
+      "dsb ish",
+      in(reg) operand
+    )
+  };
+  isb();
+}
+
+/// Invalidate all stage 1 TLB entries of the EL1&0 translation regime of the current VMID
+#[inline]
+pub fn invalidate_all_el1() {
+  unsafe { asm!("dsb ishst", "tlbi vmalle1is", "dsb ish") };
+  isb();
+}
+
+/// Invalidate all stage 1 TLB entries of the EL1&0 translation regime of the current VMID on the current core only
+#[inline]
+pub fn invalidate_all_el1_local() {
+  unsafe { asm!("dsb nshst", "tlbi vmalle1", "dsb nsh") };
+  isb();
+}
@@ -0,0 +1,241 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Per Core Data
+//!
+//! The ``PerCpu`` holds one instance of a data structure for each core. Each core stores the address of its own
+//! instance in a thread ID register once at boot time. Any later access is a simple read of this register.
+//!
+//! As there is only one thread ID register per exception level there should only be one ``PerCpu`` per exception
+//! level holding all per core data.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::percpu::*;
+//! # use ruspiro_arch_aarch64::register::el1::mpidr_el1::Topology;
+//! # use core::sync::atomic::{AtomicU32, Ordering};
+//! struct CoreData {
+//!     ticks: AtomicU32,
+//! }
+//!
+//! // a single cluster of 4 cores that are not multithreaded
+//! static CORE_DATA: PerCpu<CoreData, 4> = PerCpu::new(
+//!     [
+//!         CoreData { ticks: AtomicU32::new(0) },
+//!         CoreData { ticks: AtomicU32::new(0) },
+//!         CoreData { ticks: AtomicU32::new(0) },
+//!         CoreData { ticks: AtomicU32::new(0) },
+//!     ],
+//!     Topology::new(4, 1),
+//! );
+//!
+//! // run on each core while booting
+//! CORE_DATA.init();
+//!
+//! // access the data of the current core
+//! CORE_DATA.get().unwrap().ticks.fetch_add(1, Ordering::Relaxed);
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::register::el1::mpidr_el1::Affinity;
+use crate::register::el1::mpidr_el1::Topology;
+#[cfg(target_arch = "aarch64")]
+use crate::register::{el1::tpidr_el1, el2::tpidr_el2};
+use core::marker::PhantomData;
+
+/// Access to the thread pointer and the index of the current core used by ``PerCpu``
+pub trait ThreadPointer {
+  /// The linear index of the current core within the given topology
+  fn core_index(topology: Topology) -> usize;
+  /// Read the thread pointer of the current core
+  fn get() -> usize;
+  /// Write the thread pointer of the current core
+  fn set(ptr: usize);
+}
+
+/// Thread pointer stored in ``TPIDR_EL1``
+pub struct El1ThreadPointer;
+
+#[cfg(target_arch = "aarch64")]
+impl ThreadPointer for El1ThreadPointer {
+  #[inline]
+  fn core_index(topology: Topology) -> usize {
+    Affinity::current().to_linear_index(topology)
+  }
+
+  #[inline]
+  fn get() -> usize {
+    tpidr_el1::get() as usize
+  }
+
+  #[inline]
+  fn set(ptr: usize) {
+    tpidr_el1::set(ptr as u64);
+  }
+}
+
+/// Thread pointer stored in ``TPIDR_EL2``
+pub struct El2ThreadPointer;
+
+#[cfg(target_arch = "aarch64")]
+impl ThreadPointer for El2ThreadPointer {
+  #[inline]
+  fn core_index(topology: Topology) -> usize {
+    El1ThreadPointer::core_index(topology)
+  }
+
+  #[inline]
+  fn get() -> usize {
+    tpidr_el2::get() as usize
+  }
+
+  #[inline]
+  fn set(ptr: usize) {
+    tpidr_el2::set(ptr as u64);
+  }
+}
+
+/// One instance of ``T`` for each of the ``N`` cores. The instance of the current core is accessed using the thread
+/// pointer ``P``, which defaults to ``TPIDR_EL1``. The instance of a core is selected by its linear index within the
+/// ``Topology`` of the system.
+pub struct PerCpu<T, const N: usize, P = El1ThreadPointer> {
+  data: [T; N],
+  topology: Topology,
+  _tp: PhantomData<P>,
+}
+
+// each core only accesses it's own instance
+unsafe impl<T: Send, const N: usize, P: ThreadPointer> Sync for PerCpu<T, N, P> {}
+
+impl<T, const N: usize, P: ThreadPointer> PerCpu<T, N, P> {
+  /// Create the per core data with the given instances, one for each core of the ``topology``
+  pub const fn new(data: [T; N], topology: Topology) -> Self {
+    Self {
+      data,
+      topology,
+      _tp: PhantomData,
+    }
+  }
+
+  /// Initialize the thread pointer of the current core to point to its instance. This need to be called once on each
+  /// core before the data is accessed.
+  ///
+  /// # Panics
+  /// If the index of the current core exceeds the number of instances.
+  pub fn init(&self) {
+    let core = P::core_index(self.topology);
+    assert!(
+      core < N,
+      "core index exceeds the number of per core instances"
+    );
+    P::set(&self.data[core] as *const T as usize);
+  }
+
+  /// Get the instance of the current core. Returns ``None`` if the thread pointer of the current core does not point
+  /// to an instance of this data, because ``init`` was not called on this core or the thread pointer is shared with
+  /// another ``PerCpu``.
+  #[inline]
+  pub fn get(&self) -> Option<&T> {
+    let ptr = P::get() as *const T;
+    if self.data.as_ptr_range().contains(&ptr) {
+      // the thread pointer is only set by ``init`` to the start of an instance of this data
+      Some(unsafe { &*ptr })
+    } else {
+      None
+    }
+  }
+
+  /// Get the instance of a specific core
+  ///
+  /// # Safety
+  /// The instance of the core given is also accessed by this core. The caller need to ensure proper synchronization.
+  #[inline]
+  pub unsafe fn for_core(&self, core: usize) -> &T {
+    &self.data[core]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use core::cell::Cell;
+
+  const MOCK_MAX_CORES: usize = 8;
+  const TOPOLOGY: Topology = Topology::new(4, 1);
+
+  std::thread_local! {
+    static MOCK_CORE: Cell<usize> = const { Cell::new(0) };
+    static MOCK_TP: Cell<[usize; MOCK_MAX_CORES]> = const { Cell::new([0; MOCK_MAX_CORES]) };
+  }
+
+  /// Thread pointer that does not access any system register, simulating the current core with ``set_core``. Each
+  /// test thread simulates its own set of cores.
+  struct MockThreadPointer;
+
+  impl MockThreadPointer {
+    fn set_core(core: usize) {
+      MOCK_CORE.with(|c| c.set(core));
+    }
+  }
+
+  impl ThreadPointer for MockThreadPointer {
+    fn core_index(_topology: Topology) -> usize {
+      MOCK_CORE.with(|c| c.get())
+    }
+
+    fn get() -> usize {
+      MOCK_TP.with(|tp| tp.get()[MOCK_CORE.with(|c| c.get())])
+    }
+
+    fn set(ptr: usize) {
+      MOCK_TP.with(|tp| {
+        let mut value = tp.get();
+        value[MOCK_CORE.with(|c| c.get())] = ptr;
+        tp.set(value);
+      });
+    }
+  }
+
+  #[test]
+  fn each_core_gets_its_instance() {
+    let data: PerCpu<u32, 4, MockThreadPointer> = PerCpu::new([10, 11, 12, 13], TOPOLOGY);
+    for core in 0..4 {
+      MockThreadPointer::set_core(core);
+      data.init();
+    }
+    for core in (0..4).rev() {
+      MockThreadPointer::set_core(core);
+      assert_eq!(data.get(), Some(&(10 + core as u32)));
+    }
+  }
+
+  #[test]
+  fn uninitialized_core_gets_none() {
+    let data: PerCpu<u32, 2, MockThreadPointer> = PerCpu::new([0, 1], TOPOLOGY);
+    MockThreadPointer::set_core(0);
+    data.init();
+    MockThreadPointer::set_core(1);
+    assert_eq!(data.get(), None);
+  }
+
+  #[test]
+  fn thread_pointer_of_other_data_gets_none() {
+    let first: PerCpu<u32, 2, MockThreadPointer> = PerCpu::new([0, 1], TOPOLOGY);
+    let second: PerCpu<u32, 2, MockThreadPointer> = PerCpu::new([2, 3], TOPOLOGY);
+    MockThreadPointer::set_core(1);
+    first.init();
+    assert_eq!(first.get(), Some(&1));
+    assert_eq!(second.get(), None);
+  }
+
+  #[test]
+  #[should_panic]
+  fn init_panics_for_core_exceeding_instances() {
+    let data: PerCpu<u32, 2, MockThreadPointer> = PerCpu::new([0, 1], TOPOLOGY);
+    MockThreadPointer::set_core(2);
+    data.init();
+  }
+}
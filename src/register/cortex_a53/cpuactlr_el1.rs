@@ -0,0 +1,92 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CPUACTLR_EL1 - CPU Auxiliary Control Register EL1
+//!
+//! Provides *implementation defined* configuration and control options for the Cortex-A53 core.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::CPUACTLR_EL1`` and ``ACTLR_EL2::CPUACTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cpuactlr_el1<u64> ENCODING(S3_1_C15_C2_0) {
+        /// Disable optimized Data Memory Barrier behavior
+        DODMBS OFFSET(10) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// L1 data prefetch control. Maximum number of outstanding data prefetches allowed in the L1 memory system
+        L1PCTL OFFSET(13) BITS(3) [
+            /// Prefetch disabled
+            DISABLE = 0b000,
+            _1 = 0b001,
+            _2 = 0b010,
+            _3 = 0b011,
+            _4 = 0b100,
+            _5 = 0b101,
+            _6 = 0b110,
+            _8 = 0b111
+        ],
+        /// Enable device split throttle
+        STRIDE OFFSET(17),
+        /// Disable streaming all-allocation hints
+        DSTDIS OFFSET(18),
+        /// Number of independent data prefetch streams
+        NPFSTRM OFFSET(19) BITS(2) [
+            _1 = 0b00,
+            _2 = 0b01,
+            _3 = 0b10,
+            _4 = 0b11
+        ],
+        /// Disable instruction fetch unit throttling
+        IFUTHDIS OFFSET(21),
+        /// Disable prefetch streams initiated from store buffer accesses
+        STBPFDIS OFFSET(22),
+        /// Disable ReadUnique request for prefetch streams initiated by store buffer accesses
+        STBPFRS OFFSET(23),
+        /// Disable transient allocation hint
+        DTAH OFFSET(24) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Write streaming no-L1-allocate threshold
+        L1RADIS OFFSET(25) BITS(2) [
+            /// 4th consecutive streaming cache line does not allocate in the L1 cache
+            _4TH = 0b00,
+            /// 64th consecutive streaming cache line does not allocate in the L1 cache
+            _64TH = 0b01,
+            /// 128th consecutive streaming cache line does not allocate in the L1 cache
+            _128TH = 0b10,
+            /// Disables streaming. All write-allocate lines allocate in the L1 cache
+            DISABLE = 0b11
+        ],
+        /// Write streaming no-allocate threshold
+        RADIS OFFSET(27) BITS(2) [
+            /// 16th consecutive streaming cache line does not allocate in the L1 or L2 cache
+            _16TH = 0b00,
+            /// 128th consecutive streaming cache line does not allocate in the L1 or L2 cache
+            _128TH = 0b01,
+            /// 512th consecutive streaming cache line does not allocate in the L1 or L2 cache
+            _512TH = 0b10,
+            /// Disables streaming. All write-allocate lines allocate in the L1 or L2 cache
+            DISABLE = 0b11
+        ],
+        /// Enable data cache clean as data cache clean/invalidate
+        ENDCCASCI OFFSET(44) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ]
+    }
+}
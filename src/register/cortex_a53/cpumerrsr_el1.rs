@@ -0,0 +1,54 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CPUMERRSR_EL1 - CPU Memory Error Syndrome Register EL1
+//!
+//! Holds the ECC error information of the L1 data cache, the L1 instruction cache and the TLB RAMs of the
+//! Cortex-A53 core. Writing all zeros clears the register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cpumerrsr_el1<u64> ENCODING(S3_1_C15_C2_2) {
+        /// Indicates the index address of the first memory error
+        ADDR OFFSET(0) BITS(12),
+        /// Indicates the core and way of the RAM where the first memory error occurred
+        CPUIDWAY OFFSET(18) BITS(6),
+        /// RAM identifier. Indicates the RAM in which the first memory error occurred
+        RAMID OFFSET(24) BITS(7) [
+            /// L1 data tag RAM
+            L1_DTAG = 0x08,
+            /// L1 data data RAM
+            L1_DDATA = 0x09,
+            /// L1 data dirty RAM
+            L1_DDIRTY = 0x0A,
+            /// TLB RAM
+            TLB = 0x18
+        ],
+        /// Indicates that the register holds a valid error
+        VALID OFFSET(31) [
+            INVALID = 0b0,
+            VALID = 0b1
+        ],
+        /// Number of times an error was seen at the same RAM and location
+        REPEATERR OFFSET(32) BITS(8),
+        /// Number of times an error was seen at a different RAM or location
+        OTHERERR OFFSET(40) BITS(8),
+        /// Indicates a fatal error has been seen
+        FATAL OFFSET(63) [
+            NONE = 0b0,
+            FATAL = 0b1
+        ]
+    }
+}
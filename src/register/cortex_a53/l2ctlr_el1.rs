@@ -0,0 +1,43 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # L2CTLR_EL1 - L2 Control Register EL1
+//!
+//! Provides *implementation defined* control options for the L2 memory system of the Cortex-A53 cluster.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::L2CTLR_EL1`` and ``ACTLR_EL2::L2CTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @l2ctlr_el1<u32> ENCODING(S3_1_C11_C0_2) {
+        /// SCU-L2 cache protection. Indicates whether ECC is supported for the L2 cache and the SCU
+        SCU_L2_PROT OFFSET(21) [
+            NOT_SUPPORTED = 0b0,
+            SUPPORTED = 0b1
+        ],
+        /// CPU cache protection. Indicates whether ECC is supported for the L1 caches of the cores
+        CPU_PROT OFFSET(22) [
+            NOT_SUPPORTED = 0b0,
+            SUPPORTED = 0b1
+        ],
+        /// Number of cores in the cluster - 1
+        NUMCORES OFFSET(24) BITS(2) [
+            _1 = 0b00,
+            _2 = 0b01,
+            _3 = 0b10,
+            _4 = 0b11
+        ]
+    }
+}
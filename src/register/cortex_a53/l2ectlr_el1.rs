@@ -0,0 +1,49 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # L2ECTLR_EL1 - L2 Extended Control Register EL1
+//!
+//! Provides additional *implementation defined* control options for the L2 memory system of the Cortex-A53 cluster.
+//! The asynchronous error flags are cleared by writing a 0 to them.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::L2ECTLR_EL1`` and ``ACTLR_EL2::L2ECTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @l2ectlr_el1<u32> ENCODING(S3_1_C11_C0_3) {
+        /// L2 dynamic retention control. Number of Generic Timer ticks required before the L2 RAMs enter retention
+        /// state
+        L2RETCTL OFFSET(0) BITS(3) [
+            DISABLE = 0b000,
+            _2_TICKS = 0b001,
+            _8_TICKS = 0b010,
+            _32_TICKS = 0b011,
+            _64_TICKS = 0b100,
+            _128_TICKS = 0b101,
+            _256_TICKS = 0b110,
+            _512_TICKS = 0b111
+        ],
+        /// Internal asynchronous error, caused by an L2 RAM double-bit ECC error
+        INTERR OFFSET(29) [
+            NONE = 0b0,
+            PENDING = 0b1
+        ],
+        /// AXI or ACE asynchronous error, caused by an external bus error response
+        EXTERR OFFSET(30) [
+            NONE = 0b0,
+            PENDING = 0b1
+        ]
+    }
+}
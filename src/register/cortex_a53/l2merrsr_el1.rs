@@ -0,0 +1,52 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # L2MERRSR_EL1 - L2 Memory Error Syndrome Register EL1
+//!
+//! Holds the ECC error information of the L2 data and tag RAMs and the SCU snoop filter RAMs of the Cortex-A53
+//! cluster. Writing all zeros clears the register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @l2merrsr_el1<u64> ENCODING(S3_1_C15_C2_3) {
+        /// Indicates the index address of the first memory error
+        INDEX OFFSET(3) BITS(14),
+        /// Indicates the core and way of the RAM where the first memory error occurred
+        CPUIDWAY OFFSET(18) BITS(4),
+        /// RAM identifier. Indicates the RAM in which the first memory error occurred
+        RAMID OFFSET(24) BITS(5) [
+            /// L2 tag RAM
+            L2_TAG = 0x10,
+            /// L2 data RAM
+            L2_DATA = 0x11,
+            /// SCU snoop filter RAM
+            SCU_SNOOP = 0x12
+        ],
+        /// Indicates that the register holds a valid error
+        VALID OFFSET(31) [
+            INVALID = 0b0,
+            VALID = 0b1
+        ],
+        /// Number of times an error was seen at the same RAM and location
+        REPEATERR OFFSET(32) BITS(8),
+        /// Number of times an error was seen at a different RAM or location
+        OTHERERR OFFSET(40) BITS(8),
+        /// Indicates a fatal error has been seen
+        FATAL OFFSET(63) [
+            NONE = 0b0,
+            FATAL = 0b1
+        ]
+    }
+}
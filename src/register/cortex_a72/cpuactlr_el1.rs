@@ -0,0 +1,46 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CPUACTLR_EL1 - CPU Auxiliary Control Register EL1
+//!
+//! Provides *implementation defined* configuration and control options for the Cortex-A72 core.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::CPUACTLR_EL1`` and ``ACTLR_EL2::CPUACTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cpuactlr_el1<u64> ENCODING(S3_1_C15_C2_0) {
+        /// Disable instruction prefetch
+        DIPF OFFSET(32) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Enable data cache clean as data cache clean/invalidate
+        ENDCCASCI OFFSET(44) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Disable non-allocate hint of Write-Back Read-Allocate Write-Allocate (WBRAWA) memory
+        NCSE OFFSET(49) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Disable L1 data cache hardware prefetcher
+        DL1DPF OFFSET(56) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ]
+    }
+}
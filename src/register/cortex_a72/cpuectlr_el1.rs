@@ -0,0 +1,69 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CPUECTLR_EL1 - CPU Extended Control Register EL1
+//!
+//! Provides additional *implementation defined* configuration and control options for the Cortex-A72 core.
+//!
+//! The ``SMPEN`` bit need to be set before the caches and the MMU are enabled, otherwise the core will not take part
+//! in the data coherency.
+//! ```no_run
+//! # use ruspiro_arch_aarch64::register::cortex_a72::*;
+//! cpuectlr_el1::write(cpuectlr_el1::SMPEN::ENABLE);
+//! ```
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::CPUECTLR_EL1`` and ``ACTLR_EL2::CPUECTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cpuectlr_el1<u64> ENCODING(S3_1_C15_C2_1) {
+        /// CPU retention control. Number of Generic Timer ticks required before the core enters retention state
+        CPURETCTL OFFSET(0) BITS(3) [
+            DISABLE = 0b000,
+            _2_TICKS = 0b001,
+            _8_TICKS = 0b010,
+            _32_TICKS = 0b011,
+            _64_TICKS = 0b100,
+            _128_TICKS = 0b101,
+            _256_TICKS = 0b110,
+            _512_TICKS = 0b111
+        ],
+        /// Enables the core to receive instruction cache and TLB maintenance operations broadcast from other cores
+        /// in the cluster
+        SMPEN OFFSET(6) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// L2 load/store data prefetch distance. Number of additional requests the L2 data prefetcher issues
+        L2DPFDIST OFFSET(32) BITS(2) [
+            _16_REQUESTS = 0b00,
+            _18_REQUESTS = 0b01,
+            _20_REQUESTS = 0b10,
+            _22_REQUESTS = 0b11
+        ],
+        /// L2 instruction fetch prefetch distance. Number of additional requests the L2 instruction prefetcher issues
+        L2IPFDIST OFFSET(35) BITS(2) [
+            DISABLE = 0b00,
+            _1_REQUEST = 0b01,
+            _2_REQUESTS = 0b10,
+            _3_REQUESTS = 0b11
+        ],
+        /// Disable table walk descriptor access prefetch
+        DTWDAP OFFSET(38) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ]
+    }
+}
@@ -0,0 +1,46 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # L2ACTLR_EL1 - L2 Auxiliary Control Register EL1
+//!
+//! Provides configuration and control options for the L2 memory system of the Cortex-A72 cluster.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::L2ACTLR_EL1`` and ``ACTLR_EL2::L2ACTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @l2actlr_el1<u32> ENCODING(S3_1_C15_C0_0) {
+        /// Disable clean evict push to external
+        DCEPE OFFSET(3) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Disable ACE shareable or CHI snoopable transactions from the master
+        DACESH OFFSET(6) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Enable hazard detection timeout
+        EHDT OFFSET(7) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Enables UniqueClean evictions with data
+        ENUCE OFFSET(14) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ]
+    }
+}
@@ -0,0 +1,73 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # L2CTLR_EL1 - L2 Control Register EL1
+//!
+//! Provides *implementation defined* control options for the L2 memory system of the Cortex-A72 cluster.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Write access from EL1 and EL2 requires ``ACTLR_EL3::L2CTLR_EL1`` and ``ACTLR_EL2::L2CTLR_EL1`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @l2ctlr_el1<u32> ENCODING(S3_1_C11_C0_2) {
+        /// L2 data RAM latency
+        DATA_RAM_LAT OFFSET(0) BITS(3) [
+            _2_CYCLES = 0b000,
+            _3_CYCLES = 0b010,
+            _4_CYCLES = 0b011,
+            _5_CYCLES = 0b100,
+            _6_CYCLES = 0b101,
+            _7_CYCLES = 0b110,
+            _8_CYCLES = 0b111
+        ],
+        /// L2 data RAM setup. Adds one cycle setup to the L2 data RAMs
+        DATA_RAM_SETUP OFFSET(5) [
+            NO_SETUP = 0b0,
+            _1_CYCLE = 0b1
+        ],
+        /// L2 tag RAM latency
+        TAG_RAM_LAT OFFSET(6) BITS(3) [
+            _2_CYCLES = 0b000,
+            _3_CYCLES = 0b010,
+            _4_CYCLES = 0b011,
+            _5_CYCLES = 0b100,
+            _6_CYCLES = 0b101,
+            _7_CYCLES = 0b110,
+            _8_CYCLES = 0b111
+        ],
+        /// L2 tag RAM setup. Adds one cycle setup to the L2 tag RAMs
+        TAG_RAM_SETUP OFFSET(9) [
+            NO_SETUP = 0b0,
+            _1_CYCLE = 0b1
+        ],
+        /// Data inline ECC enable, only applicable if ECC is enabled
+        DATA_INLINE_ECC OFFSET(20) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// ECC and parity enable
+        ECC OFFSET(21) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Number of cores in the cluster - 1
+        NUMCORES OFFSET(24) BITS(2) [
+            _1 = 0b00,
+            _2 = 0b01,
+            _3 = 0b10,
+            _4 = 0b11
+        ]
+    }
+}
@@ -0,0 +1,21 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Cortex-A72 - Implementation Defined Register
+//!
+//! Each of the contained modules represents an *implementation defined* system register of the Cortex-A72 core, as
+//! for example used in the Raspberry Pi 4. Those register does not have an assembler mnemonic and are accessed using
+//! their ``S3_1_C<n>_C<m>_<op2>`` encoding. Write access from EL1 and EL2 need to be granted with the corresponding
+//! bits in ``ACTLR_EL2`` and ``ACTLR_EL3``.
+
+pub mod cpuactlr_el1;
+pub mod cpuectlr_el1;
+pub mod cpumerrsr_el1;
+pub mod l2actlr_el1;
+pub mod l2ctlr_el1;
+pub mod l2ectlr_el1;
+pub mod l2merrsr_el1;
@@ -0,0 +1,55 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # DAIF - Interrupt Mask Bits
+//!
+//! Allows access to the interrupt mask bits of the current PSTATE. The ``interrupt`` module provides convinient
+//! functions to mask and unmask the single exception types.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::register::*;
+//!
+//! if daif::read(daif::I::Field) == daif::I::MASKED {
+//!     /* IRQ's are currently masked */
+//! }
+//! ```
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+//! Access from EL0 requires ``SCTLR_EL1::UMA`` to be set.
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @daif<u64> {
+        /// FIQ mask bit
+        F OFFSET(6) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// IRQ mask bit
+        I OFFSET(7) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// SError interrupt mask bit
+        A OFFSET(8) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// Watchpoint, Breakpoint, and Software Step exceptions targeted at the current exception level mask bit
+        D OFFSET(9) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ]
+    }
+}
@@ -0,0 +1,32 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # DIT - Data Independent Timing
+//!
+//! Allows access to the Data Independent Timing bit of the current PSTATE. This requires ARMv8.4 or higher.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @dit<u64> ENCODING(S3_3_C4_C2_5) {
+        /// Data Independent Timing
+        DIT OFFSET(24) [
+            /// The architecture makes no statement about the timing properties of any instructions
+            DISABLE = 0b0,
+            /// The architecture requires that the timing of specific instructions is independent of the values
+            /// of the data being operated on
+            ENABLE = 0b1
+        ]
+    }
+}
@@ -0,0 +1,39 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CNTV_CTL_EL0 - Counter-timer Virtual Timer Control Register
+//!
+//! Control register for the virtual timer.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cntv_ctl_el0<u64> {
+        /// Enables the timer
+        ENABLE  OFFSET(0) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Timer interrupt mask
+        IMASK   OFFSET(1) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// The status of the timer condition
+        ISTATUS OFFSET(2) [
+            NOT_MET = 0b0,
+            MET = 0b1
+        ]
+    }
+}
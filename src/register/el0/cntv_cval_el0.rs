@@ -0,0 +1,22 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CNTV_CVAL_EL0 - Counter-timer Virtual Timer CompareValue Register
+//!
+//! Holds the compare value for the virtual timer.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cntv_cval_el0<u64> {}
+}
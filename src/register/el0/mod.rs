@@ -10,4 +10,9 @@
 //! Each iof the contained modules represents a system register and allows access to it. Please refer to the module
 //! documtation to get details about the contained register.
 
+pub mod cntv_ctl_el0;
+pub mod cntv_cval_el0;
 pub mod ctr_el0;
+pub mod sp_el0;
+pub mod tpidr_el0;
+pub mod tpidrro_el0;
@@ -0,0 +1,23 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SP_EL0 - Stack Pointer EL0
+//!
+//! Holds the stack pointer of EL0. It can be accessed by name from higher exception levels while they use their own
+//! stack pointer, see ``SPSel``.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @sp_el0<u64> {}
+}
@@ -0,0 +1,23 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TPIDR_EL0 - Software Thread ID Register EL0
+//!
+//! Provides a location where software executing at EL0 can store thread identifying information, for OS management
+//! purposes.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @tpidr_el0<u64> {}
+}
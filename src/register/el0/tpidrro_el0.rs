@@ -0,0 +1,23 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TPIDRRO_EL0 - Read-Only Software Thread ID Register EL0
+//!
+//! Provides a location where software executing at EL1 or higher can store thread identifying information that is
+//! visible to software executing at EL0, for OS management purposes.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R   | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @tpidrro_el0<u64> {}
+}
@@ -0,0 +1,22 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # AFSR0_EL1 - Auxiliary Fault Status Register 0 EL1
+//!
+//! Provides additional *implementation defined* fault status information for exceptions taken to EL1.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @afsr0_el1<u64> {}
+}
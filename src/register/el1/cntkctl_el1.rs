@@ -0,0 +1,56 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CNTKCTL_EL1 - Counter-timer Kernel Control Register
+//!
+//! Controls the generation of an event stream from the virtual counter and access from EL0 to the generic timer.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cntkctl_el1<u64> {
+        /// Traps EL0 accesses to the frequency and physical counter register
+        EL0PCTEN OFFSET(0) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Traps EL0 accesses to the frequency and virtual counter register
+        EL0VCTEN OFFSET(1) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Enables the event stream generation from the virtual counter
+        EVNTEN  OFFSET(2) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Controls which transition of the event stream trigger bit generates an event
+        EVNTDIR OFFSET(3) [
+            ZERO_TO_ONE = 0b0,
+            ONE_TO_ZERO = 0b1
+        ],
+        /// Selects the counter bit that triggers the event stream
+        EVNTI   OFFSET(4) BITS(4),
+        /// Traps EL0 accesses to the virtual timer register
+        EL0VTEN OFFSET(8) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Traps EL0 accesses to the physical timer register
+        EL0PTEN OFFSET(9) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ]
+    }
+}
@@ -0,0 +1,64 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # DBGBCR<n>_EL1 - Debug Breakpoint Control Registers
+//!
+//! Holds the control information for the breakpoint ``n``. The register exists once per implemented breakpoint, the
+//! number is given by ``ID_AA64DFR0_EL1::BRPs``. ``get`` and ``set`` access breakpoint 0, ``get_indexed`` and
+//! ``set_indexed`` any breakpoint.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_indexed_system_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @dbgbcr_el1<u64> ENCODING(S2_0_C0_C0_5) {
+        /// Enable the breakpoint
+        E OFFSET(0) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Privilege mode control, selecting the exception levels the debug event is generated at together with
+        /// ``HMC`` and ``SSC``
+        PMC OFFSET(1) BITS(2) [
+            /// EL1 only
+            EL1 = 0b01,
+            /// EL0 only
+            EL0 = 0b10,
+            /// EL1 and EL0
+            EL1_EL0 = 0b11
+        ],
+        /// Byte address select, 0b1111 to match an A64 instruction
+        BAS OFFSET(5) BITS(4) [
+            A64 = 0b1111
+        ],
+        /// Higher mode control
+        HMC OFFSET(13),
+        /// Security state control
+        SSC OFFSET(14) BITS(2),
+        /// Linked breakpoint number
+        LBN OFFSET(16) BITS(4),
+        /// Breakpoint type
+        BT OFFSET(20) BITS(4) [
+            /// Unlinked instruction address match
+            ADDRESS = 0b0000,
+            /// Linked instruction address match
+            ADDRESS_LINKED = 0b0001,
+            /// Unlinked context ID match
+            CONTEXT = 0b0010,
+            /// Linked context ID match
+            CONTEXT_LINKED = 0b0011
+        ]
+    }
+}
+
+impl_indexed_system_register!(2, 0, 0, 5);
@@ -0,0 +1,30 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # DBGBVR<n>_EL1 - Debug Breakpoint Value Registers
+//!
+//! Holds the virtual address or context used by the breakpoint ``n`` together with ``DBGBCR<n>_EL1``. The register
+//! exists once per implemented breakpoint, the number is given by ``ID_AA64DFR0_EL1::BRPs``. ``get`` and ``set`` access
+//! breakpoint 0, ``get_indexed`` and ``set_indexed`` any breakpoint.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_indexed_system_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @dbgbvr_el1<u64> ENCODING(S2_0_C0_C0_4) {
+        /// Bits\[48:2\] of the virtual address to match
+        VA OFFSET(2) BITS(47)
+    }
+}
+
+impl_indexed_system_register!(2, 0, 0, 4);
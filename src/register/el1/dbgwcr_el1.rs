@@ -0,0 +1,64 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # DBGWCR<n>_EL1 - Debug Watchpoint Control Registers
+//!
+//! Holds the control information for the watchpoint ``n``. The register exists once per implemented watchpoint, the
+//! number is given by ``ID_AA64DFR0_EL1::WRPs``. ``get`` and ``set`` access watchpoint 0, ``get_indexed`` and
+//! ``set_indexed`` any watchpoint.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_indexed_system_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @dbgwcr_el1<u64> ENCODING(S2_0_C0_C0_7) {
+        /// Enable the watchpoint
+        E OFFSET(0) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Privilege mode control, selecting the exception levels the debug event is generated at together with
+        /// ``HMC`` and ``SSC``
+        PAC OFFSET(1) BITS(2) [
+            /// EL1 only
+            EL1 = 0b01,
+            /// EL0 only
+            EL0 = 0b10,
+            /// EL1 and EL0
+            EL1_EL0 = 0b11
+        ],
+        /// Load/store control, the kind of access the watchpoint matches
+        LSC OFFSET(3) BITS(2) [
+            LOAD = 0b01,
+            STORE = 0b10,
+            LOAD_STORE = 0b11
+        ],
+        /// Byte address select, each bit selects one byte of the double word at ``DBGWVR<n>_EL1``
+        BAS OFFSET(5) BITS(8),
+        /// Higher mode control
+        HMC OFFSET(13),
+        /// Security state control
+        SSC OFFSET(14) BITS(2),
+        /// Linked breakpoint number
+        LBN OFFSET(16) BITS(4),
+        /// Watchpoint type
+        WT OFFSET(20) [
+            UNLINKED = 0b0,
+            LINKED = 0b1
+        ],
+        /// Address mask, the number of low address bits masked from the comparison. The values 1 and 2 are reserved
+        MASK OFFSET(24) BITS(5)
+    }
+}
+
+impl_indexed_system_register!(2, 0, 0, 7);
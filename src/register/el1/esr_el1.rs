@@ -19,7 +19,7 @@ use crate::register::*;
 use crate::{define_aarch64_register, impl_system_register_rw};
 
 define_aarch64_register! {
-    @esr_el2<u32> {
+    @esr_el1<u32> {
         /// Syndrome information
         ISS OFFSET(0) BITS(24),
         /// Syndrome valid flag
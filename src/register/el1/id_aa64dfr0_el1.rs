@@ -0,0 +1,50 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # ID_AA64DFR0_EL1 - AArch64 Debug Feature Register 0
+//!
+//! Provides top level information about the debug system in AArch64 state. This is a read-only register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R        | R      | R   | R       | R
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @id_aa64dfr0_el1<u64> {
+        /// Debug architecture version
+        DebugVer OFFSET(0) BITS(4) [
+            V8 = 0b0110,
+            V8_VHE = 0b0111,
+            V8_2 = 0b1000,
+            V8_4 = 0b1001
+        ],
+        /// Trace support
+        TraceVer OFFSET(4) BITS(4),
+        /// Performance Monitors Extension version
+        PMUVer OFFSET(8) BITS(4),
+        /// Number of breakpoints, minus 1
+        BRPs OFFSET(12) BITS(4),
+        /// Number of watchpoints, minus 1
+        WRPs OFFSET(20) BITS(4),
+        /// Number of breakpoints that are context-aware, minus 1
+        CTX_CMPs OFFSET(28) BITS(4),
+        /// Statistical Profiling Extension version
+        PMSVer OFFSET(32) BITS(4),
+        /// OS Double Lock implemented
+        DoubleLock OFFSET(36) BITS(4) [
+            SUPPORTED = 0b0000,
+            NOT_SUPPORTED = 0b1111
+        ],
+        /// Armv8.4 Self-hosted Trace Extension version
+        TraceFilt OFFSET(40) BITS(4)
+    }
+}
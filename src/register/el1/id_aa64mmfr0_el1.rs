@@ -0,0 +1,68 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # ID_AA64MMFR0_EL1 - AArch64 Memory Model Feature Register 0
+//!
+//! Provides information about the implemented memory model and memory management support in AArch64 state.
+//! This is a read-only register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R        | R      | R   | R       | R
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @id_aa64mmfr0_el1<u64> {
+        /// Physical address range supported
+        PARange     OFFSET(0) BITS(4) [
+            /// 4GB address size
+            _32BITS =    0b0000,
+            /// 64GB address size
+            _36BITS =    0b0001,
+            /// 1TB address size
+            _40BITS =    0b0010,
+            /// 4TB address size
+            _42BITS =    0b0011,
+            /// 16TB address size
+            _44BITS =    0b0100,
+            /// 256TB address size
+            _48BITS =    0b0101,
+            /// 4PB address size
+            _52BITS =    0b0110
+        ],
+        /// Number of ASID bits
+        ASIDBits    OFFSET(4) BITS(4) [
+            _8BITS =     0b0000,
+            _16BITS =    0b0010
+        ],
+        /// Mixed-endian configuration support
+        BigEnd      OFFSET(8) BITS(4),
+        /// Distinction between Secure and Non-secure memory support
+        SNSMem      OFFSET(12) BITS(4),
+        /// Mixed-endian support at EL0 only
+        BigEndEL0   OFFSET(16) BITS(4),
+        /// 16KB translation granule support
+        TGran16     OFFSET(20) BITS(4) [
+            NOT_SUPPORTED = 0b0000,
+            SUPPORTED =     0b0001
+        ],
+        /// 64KB translation granule support
+        TGran64     OFFSET(24) BITS(4) [
+            SUPPORTED =     0b0000,
+            NOT_SUPPORTED = 0b1111
+        ],
+        /// 4KB translation granule support
+        TGran4      OFFSET(28) BITS(4) [
+            SUPPORTED =     0b0000,
+            NOT_SUPPORTED = 0b1111
+        ]
+    }
+}
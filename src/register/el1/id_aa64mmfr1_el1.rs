@@ -0,0 +1,51 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # ID_AA64MMFR1_EL1 - AArch64 Memory Model Feature Register 1
+//!
+//! Provides information about the implemented memory model and memory management support in AArch64 state.
+//! This is a read-only register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R        | R      | R   | R       | R
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @id_aa64mmfr1_el1<u64> {
+        /// Hardware updates of the access flag and dirty state support
+        HAFDBS      OFFSET(0) BITS(4) [
+            NOT_SUPPORTED = 0b0000,
+            ACCESS_FLAG =   0b0001,
+            ACCESS_DIRTY =  0b0010
+        ],
+        /// Number of VMID bits
+        VMIDBits    OFFSET(4) BITS(4) [
+            _8BITS =     0b0000,
+            _16BITS =    0b0010
+        ],
+        /// Virtualization Host Extensions support
+        VH          OFFSET(8) BITS(4) [
+            NOT_SUPPORTED = 0b0000,
+            SUPPORTED =     0b0001
+        ],
+        /// Hierarchical Permission Disables support
+        HPDS        OFFSET(12) BITS(4),
+        /// LORegions support
+        LO          OFFSET(16) BITS(4),
+        /// Privileged Access Never support
+        PAN         OFFSET(20) BITS(4),
+        /// RAS extension support for SError interrupt exceptions
+        SpecSEI     OFFSET(24) BITS(4),
+        /// Execute-never control distinction by exception level at stage 2 support
+        XNX         OFFSET(28) BITS(4)
+    }
+}
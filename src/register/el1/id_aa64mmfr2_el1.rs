@@ -0,0 +1,58 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # ID_AA64MMFR2_EL1 - AArch64 Memory Model Feature Register 2
+//!
+//! Provides information about the implemented memory model and memory management support in AArch64 state.
+//! This is a read-only register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R        | R      | R   | R       | R
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @id_aa64mmfr2_el1<u64> {
+        /// Common not Private translations support
+        CnP         OFFSET(0) BITS(4),
+        /// User Access Override support
+        UAO         OFFSET(4) BITS(4) [
+            NOT_SUPPORTED = 0b0000,
+            SUPPORTED =     0b0001
+        ],
+        /// Load/Store Multiple ordering and interrupt control support in AArch32 state
+        LSM         OFFSET(8) BITS(4),
+        /// Implicit error synchronization event support
+        IESB        OFFSET(12) BITS(4),
+        /// 52Bit virtual address support
+        VARange     OFFSET(16) BITS(4),
+        /// 64Bit format of the cache size ID register support
+        CCIDX       OFFSET(20) BITS(4),
+        /// Nested virtualization support
+        NV          OFFSET(24) BITS(4),
+        /// Small translation table support
+        ST          OFFSET(28) BITS(4),
+        /// Unaligned single-copy atomicity and atomic functions support
+        AT          OFFSET(32) BITS(4),
+        /// Exception class of exceptions caused by ID register accesses
+        IDS         OFFSET(36) BITS(4),
+        /// Stage 2 forced write-back support
+        FWB         OFFSET(40) BITS(4),
+        /// TLB maintenance instructions with a translation table level hint support
+        TTL         OFFSET(48) BITS(4),
+        /// Break-before-make sequence requirement level
+        BBM         OFFSET(52) BITS(4),
+        /// Enhanced virtualization traps support
+        EVT         OFFSET(56) BITS(4),
+        /// E0PD mechanism support
+        E0PD        OFFSET(60) BITS(4)
+    }
+}
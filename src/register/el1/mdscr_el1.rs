@@ -0,0 +1,59 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # MDSCR_EL1 - Monitor Debug System Control Register
+//!
+//! Main control register for the debug implementation.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @mdscr_el1<u64> {
+        /// Software step control
+        SS OFFSET(0) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Used for save/restore of ``EDSCR.ERR``
+        ERR OFFSET(6),
+        /// Trap EL0 accesses to the debug communication channel registers
+        TDCC OFFSET(12),
+        /// Local (kernel) debug enable, enables debug exceptions at the exception level debug exceptions are taken to
+        KDE OFFSET(13) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Halting debug enable
+        HDE OFFSET(14),
+        /// Monitor debug events, enables breakpoint, watchpoint and vector catch debug exceptions
+        MDE OFFSET(15) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Sample CONTEXTIDR_EL2 with the PC sample-based profiling
+        SC2 OFFSET(19),
+        /// Trap debug register accesses to the TDA of the external debugger
+        TDA OFFSET(21),
+        /// Used for save/restore of ``EDSCR.INTdis``
+        INTdis OFFSET(22) BITS(2),
+        /// Used for save/restore of ``EDSCR.TXU``
+        TXU OFFSET(26),
+        /// Used for save/restore of ``EDSCR.RXO``
+        RXO OFFSET(27),
+        /// Used for save/restore of ``EDSCR.TXfull``
+        TXfull OFFSET(29),
+        /// Used for save/restore of ``EDSCR.RXfull``
+        RXfull OFFSET(30)
+    }
+}
@@ -10,15 +10,38 @@
 //! Each iof the contained modules represents a system register and allows access to it. Please refer to the module
 //! documtation to get details about the contained register.
 
+pub mod afsr0_el1;
+pub mod afsr1_el1;
+pub mod amair_el1;
 pub mod ccsidr_el1;
 pub mod clidr_el1;
+pub mod cntkctl_el1;
+pub mod contextidr_el1;
 pub mod cpacr_el1;
 pub mod csselr_el1;
+pub mod dbgbcr_el1;
+pub mod dbgbvr_el1;
+pub mod dbgwcr_el1;
+pub mod dbgwvr_el1;
+pub mod elr_el1;
 pub mod esr_el1;
+pub mod far_el1;
+pub mod id_aa64dfr0_el1;
+pub mod id_aa64mmfr0_el1;
+pub mod id_aa64mmfr1_el1;
+pub mod id_aa64mmfr2_el1;
 pub mod mair_el1;
+pub mod mdscr_el1;
 pub mod mpidr_el1;
+pub mod osdlr_el1;
+pub mod oslar_el1;
+pub mod oslsr_el1;
+pub mod par_el1;
 pub mod sctlr_el1;
+pub mod sp_el1;
+pub mod spsr_el1;
 pub mod tcr_el1;
+pub mod tpidr_el1;
 pub mod ttbr0_el1;
 pub mod ttbr1_el1;
 pub mod vbar_el1;
@@ -10,6 +10,16 @@
 //! Provides an additional core identification mechanism for scheduling purposes in a cluster system.
 //! This is a read-only register.
 //!
+//! The functions ``core_id``, ``cluster_id`` and ``thread_id`` respect the ``MT`` flag, so they return the proper
+//! values on multithreaded cores as well.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::register::el1::mpidr_el1;
+//! if !mpidr_el1::is_primary_core() {
+//!     /* park the secondary cores */
+//! }
+//! ```
+//!
 //! ## Usage Constraints
 //! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
 //! ----|----------|--------|-----|---------|-------
@@ -29,13 +39,224 @@ define_aarch64_register! {
         AFF2 OFFSET(16) BITS(8),
         /// Indicates whether the lowest level of affinity consists of logical cores that are
         /// implemented using a multithreading type approach
-        MT OFFSET(24),
+        MT OFFSET(24) [
+            /// Performance of cores at the lowest affinity level is largely independent
+            INDEPENDENT = 0,
+            /// Performance of cores at the lowest affinity level is very interdependent (multithreaded)
+            MULTITHREADED = 1
+        ],
         /// Indicates a single core system, as distinct from core 0 in a cluster.
         U OFFSET(30) [
             /// Core is part of a cluster
-            POC = 0
+            POC = 0,
+            /// Core is part of a uniprocessor system
+            UNIPROCESSOR = 1
         ],
         /// Affinity level 3
         AFF3 OFFSET(32) BITS(8)
     }
 }
+
+/// The packed affinity levels of a core as stored in MPIDR_EL1 together with the ``MT`` bit, which selects the affinity
+/// levels holding the thread, core and cluster. Affinities are ordered from the highest to the lowest affinity level.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Affinity {
+  levels: u32,
+  multithreaded: bool,
+}
+
+impl Affinity {
+  /// Create the affinity from the single affinity levels of a core that is not multithreaded
+  pub const fn new(aff3: u8, aff2: u8, aff1: u8, aff0: u8) -> Self {
+    Self {
+      levels: (aff3 as u32) << 24 | (aff2 as u32) << 16 | (aff1 as u32) << 8 | aff0 as u32,
+      multithreaded: false,
+    }
+  }
+
+  /// The same affinity levels with the ``MT`` bit set to the given value
+  pub const fn with_multithreading(self, multithreaded: bool) -> Self {
+    Self {
+      levels: self.levels,
+      multithreaded,
+    }
+  }
+
+  /// Extract the affinity from a raw MPIDR_EL1 value
+  pub const fn from_mpidr(mpidr: u64) -> Self {
+    Self::new(
+      (mpidr >> 32) as u8,
+      (mpidr >> 16) as u8,
+      (mpidr >> 8) as u8,
+      mpidr as u8,
+    )
+    .with_multithreading(mpidr & (1 << 24) != 0)
+  }
+
+  /// The affinity of the current core
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn current() -> Self {
+    Self::from_mpidr(get())
+  }
+
+  /// The affinity levels and the ``MT`` bit in the bit layout of MPIDR_EL1
+  pub const fn to_mpidr(&self) -> u64 {
+    ((self.levels as u64 & 0xFF00_0000) << 8)
+      | (self.levels as u64 & 0x00FF_FFFF)
+      | ((self.multithreaded as u64) << 24)
+  }
+
+  /// The value of the given affinity level 0..=3
+  pub const fn level(&self, level: u32) -> u8 {
+    (self.levels >> (level * 8)) as u8
+  }
+
+  /// Whether the lowest affinity level holds the threads of a multithreaded core, the ``MT`` bit of MPIDR_EL1. Cores
+  /// like the Cortex-A55 and Cortex-A76 set this bit even with a single thread per core, the affinity level 0 is
+  /// ``0`` in this case.
+  pub const fn is_multithreaded(&self) -> bool {
+    self.multithreaded
+  }
+
+  /// The id of the thread within its core, this is always ``0`` on cores that are not multithreaded
+  pub const fn thread(&self) -> u8 {
+    if self.multithreaded {
+      self.level(0)
+    } else {
+      0
+    }
+  }
+
+  /// The id of the core within its cluster
+  pub const fn core(&self) -> u8 {
+    if self.multithreaded {
+      self.level(1)
+    } else {
+      self.level(0)
+    }
+  }
+
+  /// The id of the cluster of the core
+  pub const fn cluster(&self) -> u8 {
+    if self.multithreaded {
+      self.level(2)
+    } else {
+      self.level(1)
+    }
+  }
+
+  /// Calculate the linear index of the core with this affinity in the given topology. This is ``0`` for the
+  /// primary core and contiguous for all cores of the system.
+  pub const fn to_linear_index(&self, topology: Topology) -> usize {
+    (self.cluster() as usize * topology.cores_per_cluster + self.core() as usize)
+      * topology.threads_per_core
+      + self.thread() as usize
+  }
+}
+
+/// The topology of the cores in the system
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Topology {
+  /// Number of cores within one cluster
+  pub cores_per_cluster: usize,
+  /// Number of threads of a core, ``1`` if the cores are not multithreaded
+  pub threads_per_core: usize,
+}
+
+impl Topology {
+  /// Create the topology description
+  pub const fn new(cores_per_cluster: usize, threads_per_core: usize) -> Self {
+    Self {
+      cores_per_cluster,
+      threads_per_core,
+    }
+  }
+}
+
+/// Check whether the lowest affinity level consists of logical threads of a multithreaded core
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn is_multithreaded() -> bool {
+  read(MT::Field) == MT::MULTITHREADED
+}
+
+/// The id of the current core within its cluster
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn core_id() -> u8 {
+  Affinity::current().core()
+}
+
+/// The id of the cluster the current core belongs to
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn cluster_id() -> u8 {
+  Affinity::current().cluster()
+}
+
+/// The id of the thread within the current core, this is always ``0`` on cores that are not multithreaded
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn thread_id() -> u8 {
+  Affinity::current().thread()
+}
+
+/// Check whether this is the primary core of the system, which is the core with all affinity levels being ``0``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn is_primary_core() -> bool {
+  let affinity = Affinity::current();
+  affinity == Affinity::new(0, 0, 0, 0).with_multithreading(affinity.is_multithreaded())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn linear_index_spans_clusters() {
+    let topology = Topology::new(4, 1);
+    assert_eq!(Affinity::new(0, 0, 0, 0).to_linear_index(topology), 0);
+    assert_eq!(Affinity::new(0, 0, 0, 3).to_linear_index(topology), 3);
+    assert_eq!(Affinity::new(0, 0, 1, 2).to_linear_index(topology), 6);
+  }
+
+  #[test]
+  fn linear_index_spans_threads() {
+    let topology = Topology::new(2, 2);
+    let affinity = |aff2, aff1, aff0| Affinity::new(0, aff2, aff1, aff0).with_multithreading(true);
+    assert_eq!(affinity(0, 0, 1).to_linear_index(topology), 1);
+    assert_eq!(affinity(0, 1, 0).to_linear_index(topology), 2);
+    assert_eq!(affinity(1, 0, 1).to_linear_index(topology), 5);
+  }
+
+  #[test]
+  fn linear_index_of_single_threaded_cores_with_mt_set() {
+    // Cortex-A55/A76 report MT=1 with the core in affinity level 1 and affinity level 0 being 0
+    let topology = Topology::new(4, 1);
+    let affinity = Affinity::from_mpidr(0x8100_0300);
+    assert!(affinity.is_multithreaded());
+    assert_eq!(
+      (affinity.cluster(), affinity.core(), affinity.thread()),
+      (0, 3, 0)
+    );
+    assert_eq!(affinity.to_linear_index(topology), 3);
+    assert_eq!(
+      Affinity::from_mpidr(0x8101_0100).to_linear_index(topology),
+      5
+    );
+  }
+
+  #[test]
+  fn mpidr_round_trip_keeps_mt() {
+    assert_eq!(
+      Affinity::from_mpidr(0x12_8134_5678).to_mpidr(),
+      0x12_0134_5678 | 1 << 24
+    );
+    assert_eq!(
+      Affinity::from_mpidr(0x12_0034_5678).to_mpidr(),
+      0x12_0034_5678
+    );
+  }
+}
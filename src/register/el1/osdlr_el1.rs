@@ -0,0 +1,29 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # OSDLR_EL1 - OS Double Lock Register
+//!
+//! Used to control the OS Double Lock, which prevents debug register accesses while the core is powered down.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @osdlr_el1<u64> {
+        /// OS Double Lock control
+        DLK OFFSET(0) [
+            UNLOCK = 0b0,
+            LOCK = 0b1
+        ]
+    }
+}
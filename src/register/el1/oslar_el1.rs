@@ -0,0 +1,30 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # OSLAR_EL1 - OS Lock Access Register
+//!
+//! Used to lock or unlock the OS Lock. While the OS Lock is locked the debug registers could be saved and restored
+//! without debug events being generated. This is a write-only register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | W        | W      | W   | W       | W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @oslar_el1<u64> ENCODING(S2_0_C1_C0_4) {
+        /// OS Lock
+        OSLK OFFSET(0) [
+            UNLOCK = 0b0,
+            LOCK = 0b1
+        ]
+    }
+}
@@ -0,0 +1,35 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # OSLSR_EL1 - OS Lock Status Register
+//!
+//! Provides the status of the OS Lock. This is a read-only register.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R        | R      | R   | R       | R
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @oslsr_el1<u64> {
+        /// OS Lock model implemented, bit\[0\] of the field
+        OSLM0 OFFSET(0),
+        /// OS Lock status
+        OSLK OFFSET(1) [
+            UNLOCKED = 0b0,
+            LOCKED = 0b1
+        ],
+        /// Not 32Bit access
+        nTT OFFSET(2),
+        /// OS Lock model implemented, bit\[1\] of the field
+        OSLM1 OFFSET(3)
+    }
+}
@@ -0,0 +1,55 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # PAR_EL1 - Physical Address Register
+//!
+//! Returns the output address or the fault information of the last successful address translation instruction. The
+//! fields ``SH``, ``NS``, ``PA`` and ``ATTR`` are valid if ``F`` is not set, the fields ``FST``, ``PTW`` and ``S`` are
+//! valid if ``F`` is set.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @par_el1<u64> {
+        /// Indicates whether the address translation instruction completed successfully
+        F       OFFSET(0) [
+            SUCCESS = 0b0,
+            ABORTED = 0b1
+        ],
+        /// Fault status code of the aborted translation
+        FST     OFFSET(1) BITS(6),
+        /// Shareability attribute of the translated address
+        SH      OFFSET(7) BITS(2) [
+            /// non shareable
+            NS =     0b00,
+            /// outer shareable
+            OS =     0b10,
+            /// inner shareable
+            IS =     0b11
+        ],
+        /// Stage 2 translation fault during a stage 1 translation table walk
+        PTW     OFFSET(8),
+        /// Non-secure attribute of the translated address
+        NS      OFFSET(9),
+        /// Stage of the translation that faulted
+        S       OFFSET(9) [
+            STAGE1 = 0b0,
+            STAGE2 = 0b1
+        ],
+        /// Output address bits\[47:12\] of the translation
+        PA      OFFSET(12) BITS(36),
+        /// Memory attributes of the translated address in the MAIR encoding
+        ATTR    OFFSET(56) BITS(8)
+    }
+}
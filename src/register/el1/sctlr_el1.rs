@@ -99,6 +99,13 @@ define_aarch64_register! {
             DONT_FORCE = 0,
             FORCE = 1
         ],
+        /// Set privileged access never on taking an exception to EL1. Requires ARMv8.1
+        SPAN    OFFSET(23) [
+            /// ``PSTATE.PAN`` is set on taking an exception to EL1
+            SET_PAN = 0,
+            /// ``PSTATE.PAN`` is unchanged on taking an exception to EL1
+            KEEP_PAN = 1
+        ],
         /// explicit data access endiannes at EL0
         E0E     OFFSET(24) [
             /// data accesses at EL0 are little-endian
@@ -21,7 +21,7 @@ use crate::register::*;
 use crate::{define_aarch64_register, impl_system_register_rw};
 
 define_aarch64_register! {
-    @tcr_el1<u32> {
+    @tcr_el1<u64> {
         /// Size offset of the memory reagion addressed by ttbr0_el1 (size = 2^(64-t0sz))
         T0SZ    OFFSET(0) BITS(6),
         /// Disable tlb walks using ttbr0_el1
@@ -66,7 +66,7 @@ define_aarch64_register! {
             _64KB =  0b10
         ],
         /// Size offset of the memory reagion addressed by ttbr1_el1 (size = 2^(64-t0sz))
-        T1SZ    OFFSET(16) BITS(5),
+        T1SZ    OFFSET(16) BITS(6),
         /// ASID definition by TTBR0_EL1 or TTBR1_EL1
         A1      OFFSET(22) [
             TTBR0 =  0b0,
@@ -128,14 +128,14 @@ define_aarch64_register! {
             _16BITS =    0b1
         ],
         /// Top Byte Ignored for ttbr0_el1
-        TBI0    OFFSET(27) [
+        TBI0    OFFSET(37) [
             /// Typ byte is used for address calculation
             USE =        0b0,
             /// Top byte is ignored for address calculation
             IGNORE =     0b1
         ],
         /// Top Byte Ignored for ttbr1_el1
-        TBI1    OFFSET(27) [
+        TBI1    OFFSET(38) [
             /// Typ byte is used for address calculation
             USE =        0b0,
             /// Top byte is ignored for address calculation
@@ -0,0 +1,25 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CNTP_CVAL_EL02 - Counter-timer Physical Timer CompareValue Register (VHE alias)
+//!
+//! Accesses the EL0 physical timer compare value from EL2 when ``HCR_EL2::E2H`` is set. With ``E2H`` set
+//! the EL1/EL0 mnemonic used at EL2 is redirected to the EL2 register.
+//!
+//! Accesses while ``HCR_EL2::E2H`` is not set are UNDEFINED.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cntp_cval_el02<u64> ENCODING(S3_5_C14_C2_2) {}
+}
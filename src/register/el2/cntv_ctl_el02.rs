@@ -0,0 +1,28 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CNTV_CTL_EL02 - Counter-timer Virtual Timer Control Register (VHE alias)
+//!
+//! Accesses ``CNTV_CTL_EL0`` of the EL1&0 translation regime from EL2 when ``HCR_EL2::E2H`` is set. With ``E2H`` set
+//! the ``CNTV_CTL_EL0`` mnemonic used at EL2 is redirected to the EL2 register. The register fields are shared with
+//! ``cntv_ctl_el0``.
+//!
+//! Accesses while ``HCR_EL2::E2H`` is not set are UNDEFINED.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+pub use crate::register::el0::cntv_ctl_el0::{ENABLE, IMASK, ISTATUS};
+
+define_aarch64_register! {
+    @cntv_ctl_el02<u64> ENCODING(S3_5_C14_C3_1) {}
+}
@@ -0,0 +1,78 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CPTR_EL2 - Architectural Feature Trap Register EL2
+//!
+//! Controls trapping to EL2 of accesses to CPACR_EL1, trace, Activity Monitor, SVE and Advanced SIMD and floating-point
+//! functionality. The layout of the register depends on ``HCR_EL2::E2H``. The fields ``TZ``, ``TFP`` and ``TTA`` are
+//! valid with ``E2H`` not set, the fields ``ZEN``, ``FPEN`` and ``TTA_E2H`` are valid with ``E2H`` set.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cptr_el2<u64> {
+        /// Reserved bits 0-7, 9, 12 and 13 that need to be written as 1 if ``HCR_EL2::E2H`` is not set
+        RES1    OFFSET(0) BITS(14) [
+            VALUE = 0x32FF
+        ],
+        /// Traps SVE instructions and register accesses to EL2
+        TZ      OFFSET(8) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Advanced SIMD and floating-point instructions and register accesses to EL2
+        TFP     OFFSET(10) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps SVE instructions and register accesses to EL2 if ``HCR_EL2::E2H`` is set
+        ZEN     OFFSET(16) BITS(2) [
+            /// trap accesses from EL0, EL1 and EL2
+            TRAP_ALL = 0b00,
+            /// trap accesses from EL0 if ``HCR_EL2::TGE`` is set
+            TRAP_EL0 = 0b01,
+            /// no accesses are trapped
+            NOTRAP = 0b11
+        ],
+        /// Traps Advanced SIMD and floating-point accesses to EL2 if ``HCR_EL2::E2H`` is set
+        FPEN    OFFSET(20) BITS(2) [
+            /// trap accesses from EL0, EL1 and EL2
+            TRAP_ALL = 0b00,
+            /// trap accesses from EL0 if ``HCR_EL2::TGE`` is set
+            TRAP_EL0 = 0b01,
+            /// no accesses are trapped
+            NOTRAP = 0b11
+        ],
+        /// Traps System register accesses to all implemented trace registers to EL2
+        TTA     OFFSET(20) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps System register accesses to all implemented trace registers to EL2 if ``HCR_EL2::E2H`` is set
+        TTA_E2H OFFSET(28) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Activity Monitor register accesses to EL2
+        TAM     OFFSET(30) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps EL1 accesses to CPACR_EL1 to EL2
+        TCPAC   OFFSET(31) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ]
+    }
+}
@@ -0,0 +1,23 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # HACR_EL2 - Hypervisor Auxiliary Control Register
+//!
+//! Controls trapping to EL2 of implementation defined aspects of Non-secure EL1 or EL0 operation. The contents of this
+//! register are *implementation defined*.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @hacr_el2<u64> {}
+}
@@ -26,6 +26,9 @@ define_aarch64_register! {
             DISABLE =    0b0,
             ENABLE =     0b1
         ],
+        /// set/way invalidation override
+        SWIO    OFFSET(1),
+        /// protected table walk
         PTW     OFFSET(2),
         /// physical FIQ routing to EL2
         FMO     OFFSET(3) [
@@ -137,6 +140,11 @@ define_aarch64_register! {
             NO_TRAP = 0b0,
             TRAP =    0b1
         ],
+        /// disable HVC instruction
+        HCD     OFFSET(29) [
+            HVC_ENABLE =  0b0,
+            HVC_DISABLE = 0b1
+        ],
         /// trap reads of virtual memory controls
         TRVM    OFFSET(30) [
             NO_TRAP = 0b0,
@@ -156,6 +164,148 @@ define_aarch64_register! {
         ID      OFFSET(33) [
             INACTIVE = 0b0,
             DISABLE_CACHE = 0b1
-        ]
+        ],
+        /// EL2 host, enables a type-2 hypervisor host operating system at EL2 (VHE)
+        E2H     OFFSET(34) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// trap LOR registers
+        TLOR    OFFSET(35) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// trap error record accesses
+        TERR    OFFSET(36) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// route synchronous external aborts to EL2
+        TEA     OFFSET(37) [
+            NO_ROUTE = 0b0,
+            ROUTE =    0b1
+        ],
+        /// mismatched inner/outer cacheable non-coherency enable for the EL1&0 translation regime
+        MIOCNCE OFFSET(38) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// enable the transactional memory instructions TSTART, TCOMMIT, TTEST and TCANCEL at EL0 and EL1
+        TME     OFFSET(39) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// trap pointer authentication key registers
+        APK     OFFSET(40) [
+            TRAP =    0b0,
+            NO_TRAP = 0b1
+        ],
+        /// trap pointer authentication instructions
+        API     OFFSET(41) [
+            TRAP =    0b0,
+            NO_TRAP = 0b1
+        ],
+        /// nested virtualization, trap EL1 execution of EL2 instructions and register accesses
+        NV      OFFSET(42) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// nested virtualization, trap EL1 accesses to EL1 registers that are redirected with E2H
+        NV1     OFFSET(43) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// trap address translation instructions from EL1
+        AT      OFFSET(44) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// nested virtualization, redirect EL2 register accesses at EL1 to memory
+        NV2     OFFSET(45) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// forced write-back, stage 2 memory attributes may force write-back cacheability
+        FWB     OFFSET(46) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// fault injection enable
+        FIEN    OFFSET(47) [
+            TRAP =    0b0,
+            NO_TRAP = 0b1
+        ],
+        /// report granule protection faults at EL0 and EL1 as aborts taken to EL2 instead of GPC exceptions to EL3
+        GPF     OFFSET(48) [
+            EL3 =   0b0,
+            EL2 =   0b1
+        ],
+        /// trap ID group 4 registers
+        TID4    OFFSET(49) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// trap instruction cache invalidation by ICIALLUIS/IC IALLUIS
+        TICAB   OFFSET(50) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// activity monitors virtual offsets enable
+        AMVOFFEN OFFSET(51) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// trap cache maintenance instructions to the Point of Unification
+        TOCU    OFFSET(52) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// enable access to the SCXTNUM_EL1 and SCXTNUM_EL0 registers
+        EnSCXT  OFFSET(53) [
+            TRAP =    0b0,
+            NO_TRAP = 0b1
+        ],
+        /// trap inner shareable TLB maintenance instructions
+        TTLBIS  OFFSET(54) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// trap outer shareable TLB maintenance instructions
+        TTLBOS  OFFSET(55) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// allocation tag access
+        ATA     OFFSET(56) [
+            TRAP =    0b0,
+            NO_TRAP = 0b1
+        ],
+        /// default cacheability tagging
+        DCT     OFFSET(57) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// trap ID group 5 registers
+        TID5    OFFSET(58) [
+            NO_TRAP = 0b0,
+            TRAP =    0b1
+        ],
+        /// delayed trapping of WFE instructions enable
+        TWEDEn  OFFSET(59) [
+            DISABLE =   0b0,
+            ENABLE =    0b1
+        ],
+        /// delay applied to trapping of WFE instructions
+        TWEDEL  OFFSET(60) BITS(4)
     }
 }
+
+/// Check whether EL2 runs a type-2 hypervisor host operating system. This is the case if ``HCR_EL2::E2H`` and
+/// ``HCR_EL2::TGE`` are set. EL0 is then part of the EL2&0 translation regime and exceptions from EL0 are routed to
+/// EL2
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn is_vhe_host() -> bool {
+  read(E2H::Field) == E2H::ENABLE && read(TGE::Field) == TGE::TRAP
+}
@@ -0,0 +1,43 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # HPFAR_EL2 - Hypervisor IPA Fault Address Register EL2
+//!
+//! Holds the faulting intermediate physical address (IPA) of translation or access faults on the stage 2 translation
+//! that are taken to EL2.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::IntermediatePhysAddr;
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @hpfar_el2<u64> {
+        /// Faulting intermediate physical address bits\[47:12\]
+        FIPA    OFFSET(4) BITS(40),
+        /// Faulting IPA address space
+        NS      OFFSET(63) [
+            SECURE = 0b0,
+            NONSECURE = 0b1
+        ]
+    }
+}
+
+/// The page aligned intermediate physical address of the last stage 2 fault taken to EL2. The offset within the page
+/// is given by the lower 12 bits of ``FAR_EL2``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn fault_ipa() -> IntermediatePhysAddr {
+  IntermediatePhysAddr::new(((get() & FIPA::Field.mask()) >> FIPA::Field.shift()) << 12)
+}
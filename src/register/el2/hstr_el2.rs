@@ -0,0 +1,108 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # HSTR_EL2 - Hypervisor System Trap Register
+//!
+//! Controls trapping to EL2 of Non-secure EL0 and EL1 accesses to AArch32 System registers in the CP15 space.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @hstr_el2<u32> {
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c0
+        T0   OFFSET(0) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c1
+        T1   OFFSET(1) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c2
+        T2   OFFSET(2) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c3
+        T3   OFFSET(3) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c5
+        T5   OFFSET(5) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c6
+        T6   OFFSET(6) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c7
+        T7   OFFSET(7) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c8
+        T8   OFFSET(8) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c9
+        T9   OFFSET(9) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c10
+        T10  OFFSET(10) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c11
+        T11  OFFSET(11) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c12
+        T12  OFFSET(12) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c13
+        T13  OFFSET(13) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Non-secure EL0 and EL1 AArch32 accesses to the System registers in the coproc == 0b1111 encoding
+        /// space with CRn == c15
+        T15  OFFSET(15) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ]
+    }
+}
@@ -0,0 +1,100 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # MDCR_EL2 - Monitor Debug Configuration Register EL2
+//!
+//! Provides EL2 configuration options for self-hosted debug and the Performance Monitors Extension.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @mdcr_el2<u64> {
+        /// Number of Performance Monitors counters accessible from EL1 and EL0
+        HPMN    OFFSET(0) BITS(5),
+        /// Traps PMCR_EL0 accesses to EL2
+        TPMCR   OFFSET(5) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Performance Monitors register accesses to EL2
+        TPM     OFFSET(6) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Enables the Performance Monitors counters reserved for EL2
+        HPME    OFFSET(7) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Routes debug exceptions from EL1 and EL0 to EL2
+        TDE     OFFSET(8) [
+            NOROUTE = 0b0,
+            ROUTE = 0b1
+        ],
+        /// Traps debug register accesses to EL2
+        TDA     OFFSET(9) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps debug OS-related register accesses to EL2
+        TDOSA   OFFSET(10) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps debug ROM address register accesses to EL2
+        TDRA    OFFSET(11) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Owning translation regime of the profiling buffer
+        E2PB    OFFSET(12) BITS(2),
+        /// Traps Statistical Profiling control register accesses to EL2
+        TPMS    OFFSET(14) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Prohibits event counting at EL2
+        HPMD    OFFSET(17) [
+            ALLOW = 0b0,
+            PROHIBIT = 0b1
+        ],
+        /// Traps trace filter control register accesses to EL2
+        TTRF    OFFSET(19) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Prohibits the cycle counter from counting at EL2
+        HCCD    OFFSET(23) [
+            ALLOW = 0b0,
+            PROHIBIT = 0b1
+        ],
+        /// Owning translation regime of the trace buffer
+        E2TB    OFFSET(24) BITS(2),
+        /// Enables long event counter overflow for the counters reserved for EL2
+        HLP     OFFSET(26) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Traps debug communication channel register accesses to EL2
+        TDCC    OFFSET(27) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Enables multi-threaded PMU event counting
+        MTPME   OFFSET(28) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ]
+    }
+}
@@ -11,10 +11,41 @@
 //! documtation to get details about the contained register.
 
 pub mod actlr_el2;
+pub mod cntkctl_el12;
+pub mod cntp_ctl_el02;
+pub mod cntp_cval_el02;
+pub mod cntp_tval_el02;
+pub mod cntv_ctl_el02;
+pub mod cntv_cval_el02;
+pub mod cntv_tval_el02;
+pub mod cntvoff_el2;
+pub mod contextidr_el12;
+pub mod cpacr_el12;
+pub mod cptr_el2;
+pub mod elr_el12;
+pub mod esr_el12;
 pub mod esr_el2;
+pub mod far_el12;
+pub mod far_el2;
+pub mod hacr_el2;
 pub mod hcr_el2;
+pub mod hpfar_el2;
+pub mod hstr_el2;
+pub mod mair_el12;
 pub mod mair_el2;
+pub mod mdcr_el2;
+pub mod sctlr_el12;
 pub mod sctlr_el2;
+pub mod spsr_el12;
+pub mod tcr_el12;
 pub mod tcr_el2;
+pub mod tpidr_el2;
+pub mod ttbr0_el12;
 pub mod ttbr0_el2;
+pub mod ttbr1_el12;
+pub mod vbar_el12;
 pub mod vbar_el2;
+pub mod vmpidr_el2;
+pub mod vpidr_el2;
+pub mod vtcr_el2;
+pub mod vttbr_el2;
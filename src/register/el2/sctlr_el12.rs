@@ -0,0 +1,30 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SCTLR_EL12 - System Control Register EL1 (VHE alias)
+//!
+//! Accesses ``SCTLR_EL1`` of the EL1&0 translation regime from EL2 when ``HCR_EL2::E2H`` is set. With ``E2H`` set
+//! the ``SCTLR_EL1`` mnemonic used at EL2 is redirected to the EL2 register. The register fields are shared with
+//! ``sctlr_el1``.
+//!
+//! Accesses while ``HCR_EL2::E2H`` is not set are UNDEFINED.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+pub use crate::register::el1::sctlr_el1::{
+  A, C, CP15EN, DZE, E0E, EE, I, ITD, M, NTWE, NTWI, SA, SA0, SED, SPAN, UCI, UCT, UMA, WXN,
+};
+
+define_aarch64_register! {
+    @sctlr_el12<u64> ENCODING(S3_5_C1_C0_0) {}
+}
@@ -0,0 +1,30 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TCR_EL12 - Translation Control Register EL1 (VHE alias)
+//!
+//! Accesses ``TCR_EL1`` of the EL1&0 translation regime from EL2 when ``HCR_EL2::E2H`` is set. With ``E2H`` set
+//! the ``TCR_EL1`` mnemonic used at EL2 is redirected to the EL2 register. The register fields are shared with
+//! ``tcr_el1``.
+//!
+//! Accesses while ``HCR_EL2::E2H`` is not set are UNDEFINED.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+pub use crate::register::el1::tcr_el1::{
+  A1, AS, EPD0, EPD1, IPS, IRGN0, IRGN1, ORGN0, ORGN1, SH0, SH1, T0SZ, T1SZ, TBI0, TBI1, TG0, TG1,
+};
+
+define_aarch64_register! {
+    @tcr_el12<u64> ENCODING(S3_5_C2_C0_2) {}
+}
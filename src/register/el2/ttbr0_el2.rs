@@ -17,6 +17,8 @@
 //!  -  | -        | -      | R/W | R/W     | R/W
 //!
 
+#[cfg(target_arch = "aarch64")]
+use crate::addr::PhysAddr;
 use crate::register::*;
 use crate::{define_aarch64_register, impl_system_register_rw};
 
@@ -29,3 +31,19 @@ define_aarch64_register! {
         ASID OFFSET(48) BITS(16)
     }
 }
+
+/// The physical base address of the translation table, without the ``CnP`` bit
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn table_base() -> PhysAddr {
+  PhysAddr::new(get() & BADDR::Field.mask() & !1)
+}
+
+/// Set the physical base address of the translation table, keeping the ``ASID``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_table_base(table: PhysAddr) {
+  set((get() & ASID::Field.mask()) | (table.as_u64() & BADDR::Field.mask()));
+}
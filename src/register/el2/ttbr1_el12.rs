@@ -0,0 +1,46 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TTBR1_EL12 - Translation Table Base Register 1 EL1 (VHE alias)
+//!
+//! Accesses ``TTBR1_EL1`` of the EL1&0 translation regime from EL2 when ``HCR_EL2::E2H`` is set. With ``E2H`` set
+//! the ``TTBR1_EL1`` mnemonic used at EL2 is redirected to the EL2 register. The register fields are shared with
+//! ``ttbr1_el1``.
+//!
+//! Accesses while ``HCR_EL2::E2H`` is not set are UNDEFINED.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::PhysAddr;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+pub use crate::register::el1::ttbr1_el1::{ASID, BADDR};
+
+define_aarch64_register! {
+    @ttbr1_el12<u64> ENCODING(S3_5_C2_C0_1) {}
+}
+
+/// The physical base address of the translation table, without the ``CnP`` bit
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn table_base() -> PhysAddr {
+  PhysAddr::new(get() & BADDR::Field.mask() & !1)
+}
+
+/// Set the physical base address of the translation table, keeping the ``ASID``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_table_base(table: PhysAddr) {
+  set((get() & ASID::Field.mask()) | (table.as_u64() & BADDR::Field.mask()));
+}
@@ -0,0 +1,47 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # VBAR_EL12 - Vector Base Address Register EL1 (VHE alias)
+//!
+//! Accesses ``VBAR_EL1`` of the EL1&0 translation regime from EL2 when ``HCR_EL2::E2H`` is set. With ``E2H`` set
+//! the ``VBAR_EL1`` mnemonic used at EL2 is redirected to the EL2 register. The register fields are shared with
+//! ``vbar_el1``.
+//!
+//! Accesses while ``HCR_EL2::E2H`` is not set are UNDEFINED.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::VirtAddr;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+pub use crate::register::el1::vbar_el1::BADDR;
+
+define_aarch64_register! {
+    @vbar_el12<u64> ENCODING(S3_5_C12_C0_0) {}
+}
+
+/// The base address of the exception vector table of EL1
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn base() -> VirtAddr {
+  VirtAddr::new(get() & BADDR::Field.mask())
+}
+
+/// Set the base address of the exception vector table of EL1, which need to be aligned to 2KB
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_base(base: VirtAddr) {
+  debug_assert!(base.is_aligned(0x800), "vector table not aligned to 2KB");
+  set(base.as_u64());
+}
@@ -15,6 +15,8 @@
 //!  -  | -        | -      | R/W | R/W     | R/W
 //!
 
+#[cfg(target_arch = "aarch64")]
+use crate::addr::VirtAddr;
 use crate::register::*;
 use crate::{define_aarch64_register, impl_system_register_rw};
 
@@ -24,3 +26,20 @@ define_aarch64_register! {
         BADDR OFFSET(11) BITS(53)
     }
 }
+
+/// The base address of the exception vector table of EL2
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn base() -> VirtAddr {
+  VirtAddr::new(get() & BADDR::Field.mask())
+}
+
+/// Set the base address of the exception vector table of EL2, which need to be aligned to 2KB
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_base(base: VirtAddr) {
+  debug_assert!(base.is_aligned(0x800), "vector table not aligned to 2KB");
+  set(base.as_u64());
+}
@@ -0,0 +1,46 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # VMPIDR_EL2 - Virtualization Multiprocessor ID Register
+//!
+//! Holds the value of the Virtualization Multiprocessor ID. This is the value returned by Non-secure EL1 reads of
+//! ``MPIDR_EL1``. The register fields are shared with ``mpidr_el1``.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+pub use crate::register::el1::mpidr_el1::{Affinity, AFF0, AFF1, AFF2, AFF3, MT, U};
+
+define_aarch64_register! {
+    @vmpidr_el2<u64> {
+        /// Reserved bit that reads as 1
+        RES1 OFFSET(31)
+    }
+}
+
+/// Set the MPIDR_EL1 value presented to the guest to the given affinity. The guest will see a multithreaded core if
+/// the ``MT`` bit of the affinity is set
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_virtual_mpidr(affinity: Affinity) {
+  set(affinity.to_mpidr() | RES1::Field.mask());
+}
+
+/// The affinity presented to the guest
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn virtual_affinity() -> Affinity {
+  Affinity::from_mpidr(get())
+}
@@ -0,0 +1,40 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # VPIDR_EL2 - Virtualization Processor ID Register
+//!
+//! Holds the value of the Virtualization Processor ID. This is the value returned by Non-secure EL1 reads of
+//! ``MIDR_EL1``.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @vpidr_el2<u64> {
+        /// Revision number of the processor
+        Revision        OFFSET(0) BITS(4),
+        /// Implementation defined primary part number
+        PartNum         OFFSET(4) BITS(12),
+        /// Architecture of the processor
+        Architecture    OFFSET(16) BITS(4) [
+            /// Architectural features are individually identified in the ID registers
+            ID_REGISTERS = 0b1111
+        ],
+        /// Variant number of the processor
+        Variant         OFFSET(20) BITS(4),
+        /// Implementer code
+        Implementer     OFFSET(24) BITS(8) [
+            ARM = 0x41
+        ]
+    }
+}
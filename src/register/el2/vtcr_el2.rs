@@ -0,0 +1,108 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # VTCR_EL2 - Virtualization Translation Control Register EL2
+//!
+//! Controls the translation table walks required for the stage 2 translation of memory accesses from Non-secure EL0
+//! and EL1 and holds cacheability and shareability information for the accesses.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @vtcr_el2<u64> {
+        /// Size offset of the memory region addressed by vttbr_el2 (size = 2^(64-t0sz))
+        T0SZ    OFFSET(0) BITS(6),
+        /// Starting level of the stage 2 translation table walk. The meaning depends on the granule size,
+        /// the values given are valid for the 4KB granule
+        SL0     OFFSET(6) BITS(2) [
+            /// start at level 2
+            LEVEL2 =    0b00,
+            /// start at level 1
+            LEVEL1 =    0b01,
+            /// start at level 0
+            LEVEL0 =    0b10
+        ],
+        /// Inner cacheability attribute for memory associated with tlb walks using vttbr_el2
+        IRGN0   OFFSET(8) BITS(2) [
+            /// normal memory, inner non-cacheable
+            NM_INC =         0b00,
+            /// normal memory, inner write-back, read-allocate, write-allocate, cacheable
+            NM_IWB_RA_WA =   0b01,
+            /// normal memory, inner write-through, read-allocate, no write-allocate, cacheable
+            NM_IWT_RA_NWA =  0b10,
+            /// normal memory, inner write-back, read-allocate, no write-allocate, caheable
+            NM_IWB_RA_NWA =  0b11
+        ],
+        /// Outer cacheability attribute for memory associated with tlb walks using vttbr_el2
+        ORGN0   OFFSET(10) BITS(2) [
+            /// normal memory, outer non-cacheable
+            NM_ONC =         0b00,
+            /// normal memory, outer write-back, read-allocate, write-allocate, cacheable
+            NM_OWB_RA_WA =   0b01,
+            /// normal memory, outer write-through, read-allocate, no write-allocate, cacheable
+            NM_OWT_RA_NWA =  0b10,
+            /// normal memory, outer write-back, read-allocate, no write-allocate, caheable
+            NM_OWB_RA_NWA =  0b11
+        ],
+        /// Shareability attribute for memory associated with tlb walks using vttbr_el2
+        SH0     OFFSET(12) BITS(2) [
+            /// non shareable
+            NS =     0b00,
+            /// outer shareable
+            OS =     0b10,
+            /// inner shareable
+            IS =     0b11
+        ],
+        /// Granule size for the vttbr_el2
+        TG0     OFFSET(14) BITS(2) [
+            _4KB =   0b00,
+            _64KB =  0b01,
+            _16KB =  0b10
+        ],
+        /// Physical address size of the second stage of translation
+        PS      OFFSET(16) BITS(3) [
+            /// 4GB address size
+            _32BITS =    0b000,
+            /// 64GB address size
+            _36BITS =    0b001,
+            /// 1TB address size
+            _40BITS =    0b010,
+            /// 4TB address size
+            _42BITS =    0b011,
+            /// 16TB address size
+            _44BITS =    0b100,
+            /// 256TB address size
+            _48BITS =    0b101
+        ],
+        /// VMID size
+        VS      OFFSET(19) [
+            /// 8 bit VMID
+            _8BIT =     0b0,
+            /// 16 bit VMID
+            _16BIT =    0b1
+        ],
+        /// Hardware management of the access flag for stage 2 translations
+        HA      OFFSET(21) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Hardware management of the dirty state for stage 2 translations
+        HD      OFFSET(22) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Reserved bit that need to be written as 1
+        RES1    OFFSET(31)
+    }
+}
@@ -0,0 +1,51 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # VTTBR_EL2 - Virtualization Translation Table Base Register EL2
+//!
+//! Holds the base address of the translation table for the initial lookup level of the stage 2 translation of memory
+//! accesses from Non-secure EL0 and EL1 and the VMID of the virtual machine the translation belongs to.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | R/W | R/W     | R/W
+//!
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::PhysAddr;
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @vttbr_el2<u64> {
+        /// Common not private
+        CnP OFFSET(0),
+        /// Translation table base address bits\[47:x\]. **x** is based on the value of ``VTCR_EL2::T0SZ``,
+        /// ``VTCR_EL2::SL0`` and the memory translation granule size
+        BADDR OFFSET(1) BITS(47),
+        /// The VMID for the translation table. If ``VTCR_EL2::VS`` selects 8 bit VMIDs the upper 8 bits are
+        /// RES0
+        VMID OFFSET(48) BITS(16)
+    }
+}
+
+/// The physical base address of the stage 2 translation table, without the ``CnP`` bit
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn table_base() -> PhysAddr {
+  PhysAddr::new(get() & BADDR::Field.mask())
+}
+
+/// Set the physical base address of the stage 2 translation table, keeping the ``VMID``
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_table_base(table: PhysAddr) {
+  set((get() & VMID::Field.mask()) | (table.as_u64() & BADDR::Field.mask()));
+}
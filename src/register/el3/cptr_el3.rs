@@ -0,0 +1,50 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # CPTR_EL3 - Architectural Feature Trap Register EL3
+//!
+//! Controls trapping to EL3 of accesses to CPACR, CPACR_EL1, HCPTR, CPTR_EL2, trace, Activity Monitor, SVE and
+//! Advanced SIMD and floating-point functionality.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @cptr_el3<u64> {
+        /// Traps SVE instructions and register accesses to EL3
+        EZ      OFFSET(8) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Traps Advanced SIMD and floating-point instructions and register accesses to EL3
+        TFP     OFFSET(10) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps System register accesses to all implemented trace registers to EL3
+        TTA     OFFSET(20) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps Activity Monitor register accesses to EL3
+        TAM     OFFSET(30) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps EL2 accesses to CPTR_EL2/HCPTR and EL1 accesses to CPACR_EL1/CPACR to EL3
+        TCPAC   OFFSET(31) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ]
+    }
+}
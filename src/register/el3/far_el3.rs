@@ -0,0 +1,33 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # FAR_EL3 - Fault Address Register EL3
+//!
+//! Holds the faulting virtual address for all synchronous instruction or data abort, PC alignment fault and
+//! watchpoint exceptions that are taken to EL3.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::VirtAddr;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @far_el3<u64> {}
+}
+
+/// The faulting virtual address of the last exception taken to EL3
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn fault_address() -> VirtAddr {
+  VirtAddr::new(get())
+}
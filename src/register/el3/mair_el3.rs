@@ -0,0 +1,81 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # MAIR_EL3 - Memory Attribute Indirection Register EL3
+//!
+//! Provides the memory attribute encodings corresponding to the possible AttrIndx values in a long-descriptor
+//! format translation table entry for stage 1 translations at EL3.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @mair_el3<u64> {
+        MAIR0 OFFSET(0) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR1 OFFSET(8) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR2 OFFSET(16) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR3 OFFSET(24) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR4 OFFSET(32) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR5 OFFSET(40) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR6 OFFSET(48) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ],
+        MAIR7 OFFSET(56) BITS(8) [
+            NGNRNE = 0x00,
+            NGNRE = 0x04,
+            GRE = 0x0C,
+            NC = 0x44,
+            NORM = 0xFF
+        ]
+    }
+}
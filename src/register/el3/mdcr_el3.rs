@@ -0,0 +1,87 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # MDCR_EL3 - Monitor Debug Configuration Register EL3
+//!
+//! Provides EL3 configuration options for self-hosted debug and the Performance Monitors Extension.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @mdcr_el3<u64> {
+        /// Traps Performance Monitors register accesses to EL3
+        TPM     OFFSET(6) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps debug register accesses to EL3
+        TDA     OFFSET(9) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps debug OS-related register accesses to EL3
+        TDOSA   OFFSET(10) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Non-secure profiling buffer
+        NSPB    OFFSET(12) BITS(2),
+        /// AArch32 secure privileged debug
+        SPD32   OFFSET(14) BITS(2) [
+            LEGACY = 0b00,
+            DISABLE = 0b10,
+            ENABLE = 0b11
+        ],
+        /// AArch64 secure self-hosted invasive debug disable
+        SDD     OFFSET(16) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Secure Performance Monitors enable
+        SPME    OFFSET(17) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Secure trace enable
+        STE     OFFSET(18) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Traps trace filter control register accesses to EL3
+        TTRF    OFFSET(19) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// External debugger access to breakpoint and watchpoint registers disabled
+        EDAD    OFFSET(20) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// External debugger access to Performance Monitors registers disabled
+        EPMAD   OFFSET(21) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Secure cycle counter disable
+        SCCD    OFFSET(23) [
+            ENABLE = 0b0,
+            DISABLE = 0b1
+        ],
+        /// Traps debug communication channel register accesses to EL3
+        TDCC    OFFSET(27) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ]
+    }
+}
@@ -11,4 +11,16 @@
 //! documtation to get details about the contained register.
 
 pub mod actlr_el3;
+pub mod cptr_el3;
+pub mod elr_el3;
 pub mod esr_el3;
+pub mod far_el3;
+pub mod mair_el3;
+pub mod mdcr_el3;
+pub mod rmr_el3;
+pub mod scr_el3;
+pub mod sctlr_el3;
+pub mod spsr_el3;
+pub mod tcr_el3;
+pub mod ttbr0_el3;
+pub mod vbar_el3;
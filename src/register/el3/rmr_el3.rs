@@ -0,0 +1,34 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # RMR_EL3 - Reset Management Register EL3
+//!
+//! Requests a warm reset and controls the execution state the core resets into.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @rmr_el3<u32> {
+        /// Execution state to use after a warm reset
+        AA64    OFFSET(0) [
+            AARCH32 = 0b0,
+            AARCH64 = 0b1
+        ],
+        /// Reset request
+        RR      OFFSET(1) [
+            NONE = 0b0,
+            REQUEST = 0b1
+        ]
+    }
+}
@@ -0,0 +1,139 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SCR_EL3 - Secure Configuration Register EL3
+//!
+//! Defines the configuration of the current security state. It specifies the security state of EL0, EL1 and EL2,
+//! the execution state at the lower exception levels and whether IRQ, FIQ and SError interrupts are taken to EL3.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @scr_el3<u64> {
+        /// Non-secure bit, security state of EL0, EL1 and EL2
+        NS      OFFSET(0) [
+            SECURE = 0b0,
+            NONSECURE = 0b1
+        ],
+        /// Physical IRQ routing
+        IRQ     OFFSET(1) [
+            /// IRQs are not taken to EL3
+            LOWER = 0b0,
+            /// IRQs at any exception level are taken to EL3
+            EL3 = 0b1
+        ],
+        /// Physical FIQ routing
+        FIQ     OFFSET(2) [
+            /// FIQs are not taken to EL3
+            LOWER = 0b0,
+            /// FIQs at any exception level are taken to EL3
+            EL3 = 0b1
+        ],
+        /// External abort and SError interrupt routing
+        EA      OFFSET(3) [
+            /// External aborts and SErrors are not taken to EL3
+            LOWER = 0b0,
+            /// External aborts and SErrors at any exception level are taken to EL3
+            EL3 = 0b1
+        ],
+        /// Reserved bits that need to be written as 1
+        RES1    OFFSET(4) BITS(2) [
+            VALUE = 0b11
+        ],
+        /// Secure Monitor Call disable
+        SMD     OFFSET(7) [
+            /// SMC instructions are enabled at EL1 and above
+            ENABLE = 0b0,
+            /// SMC instructions are UNDEFINED
+            DISABLE = 0b1
+        ],
+        /// Hypervisor Call instruction enable
+        HCE     OFFSET(8) [
+            /// HVC instructions are UNDEFINED
+            DISABLE = 0b0,
+            /// HVC instructions are enabled at EL1 and above
+            ENABLE = 0b1
+        ],
+        /// Secure instruction fetch from Non-secure memory
+        SIF     OFFSET(9) [
+            PERMIT = 0b0,
+            DENY = 0b1
+        ],
+        /// Execution state control for the next lower exception level
+        RW      OFFSET(10) [
+            /// The next lower level is AArch32
+            AARCH32 = 0b0,
+            /// The next lower level is AArch64
+            AARCH64 = 0b1
+        ],
+        /// Traps Secure EL1 accesses to the counter-timer physical registers
+        ST      OFFSET(11) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Traps WFI instructions executed below EL3 to EL3
+        TWI     OFFSET(12) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps WFE instructions executed below EL3 to EL3
+        TWE     OFFSET(13) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps LOR registers to EL3
+        TLOR    OFFSET(14) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps error record accesses to EL3
+        TERR    OFFSET(15) [
+            NOTRAP = 0b0,
+            TRAP = 0b1
+        ],
+        /// Traps pointer authentication key register accesses to EL3
+        APK     OFFSET(16) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Traps pointer authentication instructions to EL3
+        API     OFFSET(17) [
+            TRAP = 0b0,
+            NOTRAP = 0b1
+        ],
+        /// Secure EL2 enable
+        EEL2    OFFSET(18) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// External aborts to SError interrupt vector
+        EASE    OFFSET(19),
+        /// Non-maskable external aborts
+        NMEA    OFFSET(20),
+        /// Fault injection enable
+        FIEN    OFFSET(21),
+        /// Enables access to the SCXTNUM_ELx registers
+        EnSCXT  OFFSET(25),
+        /// Allocation tag access
+        ATA     OFFSET(26),
+        /// Fine-grained traps enable
+        FGTEn   OFFSET(27),
+        /// Enhanced counter virtualization enable
+        ECVEn   OFFSET(28),
+        /// Delayed trapping of WFE instructions enable
+        TWEDEn  OFFSET(29),
+        /// Delay applied to trapping of WFE instructions
+        TWEDEL  OFFSET(30) BITS(4)
+    }
+}
@@ -0,0 +1,90 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SPSR_EL3 - Saved Program Status Register EL3
+//!
+//! Holds the saved process state when an exception is taken to EL3
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @spsr_el3<u64> {
+        /// Exception level and selected stack pointer
+        M OFFSET(0) BITS(4) [
+            /// EL0 using SP_EL0
+            EL0t = 0b0000,
+            /// EL1 using SP_EL0
+            EL1t = 0b0100,
+            /// EL1 using SP_EL1
+            EL1h = 0b0101,
+            /// EL2 using SP_EL0
+            EL2t = 0b1000,
+            /// EL2 using SP_EL2
+            EL2h = 0b1001,
+            /// EL3 using SP_EL0
+            EL3t = 0b1100,
+            /// EL3 using SP_EL3
+            EL3h = 0b1101
+        ],
+        /// Execution state
+        nRW OFFSET(4) [
+            AARCH64 = 0b0,
+            AARCH32 = 0b1
+        ],
+        /// FIQ interrupt mask
+        F OFFSET(6) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// IRQ interrupt mask
+        I OFFSET(7) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// SError interrupt mask
+        A OFFSET(8) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// Debug exception mask
+        D OFFSET(9) [
+            UNMASKED = 0b0,
+            MASKED = 0b1
+        ],
+        /// Branch type indicator
+        BTYPE OFFSET(10) BITS(2),
+        /// Speculative Store Bypass Safe
+        SSBS OFFSET(12),
+        /// Illegal Execution state
+        IL OFFSET(20),
+        /// Software step
+        SS OFFSET(21),
+        /// Privileged Access Never
+        PAN OFFSET(22),
+        /// User Access Override
+        UAO OFFSET(23),
+        /// Data Independent Timing
+        DIT OFFSET(24),
+        /// Tag Check Override
+        TCO OFFSET(25),
+        /// Overflow condition flag
+        V OFFSET(28),
+        /// Carry condition flag
+        C OFFSET(29),
+        /// Zero condition flag
+        Z OFFSET(30),
+        /// Negative condition flag
+        N OFFSET(31)
+    }
+}
@@ -0,0 +1,29 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TTBR0_EL3 - Translation Table Base Register 0 EL3
+//!
+//! Holds the base address of the translation table for the stage 1 translation of memory accesses from EL3.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @ttbr0_el3<u64> {
+        /// Common not private
+        CnP OFFSET(0),
+        /// Translation table base address bits\[47:x\]. **x** is based on the value of ``TCR_EL3::T0SZ``
+        /// and the memory translation granule size
+        BADDR OFFSET(1) BITS(47)
+    }
+}
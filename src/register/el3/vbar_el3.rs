@@ -0,0 +1,45 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # VBAR_EL3 - Vector Base Address Register EL3
+//!
+//! Holds the exception base address for any exception that is taken to EL3
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | -        | -      | -   | R/W     | R/W
+//!
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::VirtAddr;
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @vbar_el3<u64> {
+        /// Base address of the exception vectors for exceptions taken in this exception level
+        BADDR OFFSET(11) BITS(53)
+    }
+}
+
+/// The base address of the exception vector table of EL3
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn base() -> VirtAddr {
+  VirtAddr::new(get() & BADDR::Field.mask())
+}
+
+/// Set the base address of the exception vector table of EL3, which need to be aligned to 2KB
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[allow(dead_code)]
+pub fn set_base(base: VirtAddr) {
+  debug_assert!(base.is_aligned(0x800), "vector table not aligned to 2KB");
+  set(base.as_u64());
+}
@@ -0,0 +1,88 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # FPCR - Floating-point Control Register
+//!
+//! Controls floating-point behavior, like the rounding mode, flush-to-zero, default NaN and the trapping of
+//! floating-point exceptions. Accesses are trapped if not enabled with ``CPACR_EL1::FPEN``.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @fpcr<u64> {
+        /// Invalid operation floating-point exception trap enable
+        IOE OFFSET(8) [
+            UNTRAPPED = 0b0,
+            TRAPPED = 0b1
+        ],
+        /// Divide by zero floating-point exception trap enable
+        DZE OFFSET(9) [
+            UNTRAPPED = 0b0,
+            TRAPPED = 0b1
+        ],
+        /// Overflow floating-point exception trap enable
+        OFE OFFSET(10) [
+            UNTRAPPED = 0b0,
+            TRAPPED = 0b1
+        ],
+        /// Underflow floating-point exception trap enable
+        UFE OFFSET(11) [
+            UNTRAPPED = 0b0,
+            TRAPPED = 0b1
+        ],
+        /// Inexact floating-point exception trap enable
+        IXE OFFSET(12) [
+            UNTRAPPED = 0b0,
+            TRAPPED = 0b1
+        ],
+        /// Input denormal floating-point exception trap enable
+        IDE OFFSET(15) [
+            UNTRAPPED = 0b0,
+            TRAPPED = 0b1
+        ],
+        /// Flush-to-zero mode control bit on half-precision data-processing instructions
+        FZ16 OFFSET(19) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Rounding mode control field
+        RMode OFFSET(22) BITS(2) [
+            /// Round to Nearest
+            RN = 0b00,
+            /// Round towards Plus Infinity
+            RP = 0b01,
+            /// Round towards Minus Infinity
+            RM = 0b10,
+            /// Round towards Zero
+            RZ = 0b11
+        ],
+        /// Flush-to-zero mode control bit
+        FZ OFFSET(24) [
+            DISABLE = 0b0,
+            ENABLE = 0b1
+        ],
+        /// Default NaN mode control bit
+        DN OFFSET(25) [
+            /// NaN operands propagate through to the output of a floating-point operation
+            PROPAGATE = 0b0,
+            /// Any operation involving one or more NaNs returns the Default NaN
+            DEFAULT_NAN = 0b1
+        ],
+        /// Alternative half-precision control bit
+        AHP OFFSET(26) [
+            IEEE = 0b0,
+            ALTERNATIVE = 0b1
+        ]
+    }
+}
@@ -0,0 +1,47 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # FPSR - Floating-point Status Register
+//!
+//! Provides floating-point system status information, like the cumulative floating-point exception flags. Accesses
+//! are trapped if not enabled with ``CPACR_EL1::FPEN``.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @fpsr<u64> {
+        /// Invalid operation cumulative floating-point exception bit
+        IOC OFFSET(0),
+        /// Divide by zero cumulative floating-point exception bit
+        DZC OFFSET(1),
+        /// Overflow cumulative floating-point exception bit
+        OFC OFFSET(2),
+        /// Underflow cumulative floating-point exception bit
+        UFC OFFSET(3),
+        /// Inexact cumulative floating-point exception bit
+        IXC OFFSET(4),
+        /// Input denormal cumulative floating-point exception bit
+        IDC OFFSET(7),
+        /// Cumulative saturation bit, Advanced SIMD only
+        QC OFFSET(27),
+        /// Overflow condition flag for AArch32 floating-point comparison operations
+        V OFFSET(28),
+        /// Carry condition flag for AArch32 floating-point comparison operations
+        C OFFSET(29),
+        /// Zero condition flag for AArch32 floating-point comparison operations
+        Z OFFSET(30),
+        /// Negative condition flag for AArch32 floating-point comparison operations
+        N OFFSET(31)
+    }
+}
@@ -17,9 +17,10 @@ macro_rules! impl_system_register_rw {
   ($t:ty) => {
     /// Update the contents of a register from the ``RegisterFieldValue`` given. This will
     /// only change the bits the ``RegisterField`` definition specifies.
+    #[cfg(target_arch = "aarch64")]
     #[inline]
     #[allow(dead_code)]
-    pub fn write(field_value: RegisterFieldValue<$t>) {
+    pub fn write(field_value: $crate::RegisterFieldValue<$t>) {
       let raw_value = (get() & !field_value.mask()) | field_value.raw_value();
       set(raw_value);
     }
@@ -30,15 +31,81 @@ macro_rules! impl_system_register_rw {
     /// For example:
     /// If register raw value is 0b10110, the returned value for a register field specified as
     /// bits\[4:3\] would be 0b01. No further "masking" or "bit-shift" required
+    #[cfg(target_arch = "aarch64")]
     #[inline]
     #[allow(dead_code)]
-    pub fn read(field: RegisterField<$t>) -> RegisterFieldValue<$t> {
+    pub fn read(field: $crate::RegisterField<$t>) -> $crate::RegisterFieldValue<$t> {
       let raw_value = get() & field.mask();
-      RegisterFieldValue::<$t>::new(field, raw_value >> field.shift())
+      $crate::RegisterFieldValue::<$t>::new(field, raw_value >> field.shift())
     }
   };
 }
 
+/// Helper macro to implement the access to the instances of a system register that exists multiple times with the
+/// instance number encoded in CRm, like the debug breakpoint and watchpoint registers ``DBGBVR<n>_EL1``
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_indexed_system_register {
+  ($op0:literal, $op1:literal, $crn:literal, $op2:literal) => {
+    /// Read the raw contents of the register instance ``n``
+    ///
+    /// # Panics
+    /// If ``n`` exceeds the 16 possible instances
+    #[cfg(target_arch = "aarch64")]
+    #[inline]
+    #[allow(dead_code)]
+    pub fn get_indexed(n: usize) -> u64 {
+      $crate::impl_indexed_system_register!(@instance n, $op0, $op1, $crn, $op2, get())
+    }
+
+    /// Write the raw contents of the register instance ``n``
+    ///
+    /// # Panics
+    /// If ``n`` exceeds the 16 possible instances
+    #[cfg(target_arch = "aarch64")]
+    #[inline]
+    #[allow(dead_code)]
+    pub fn set_indexed(n: usize, raw_value: u64) {
+      $crate::impl_indexed_system_register!(@instance n, $op0, $op1, $crn, $op2, set(raw_value))
+    }
+  };
+  (@instance $n:ident, $op0:literal, $op1:literal, $crn:literal, $op2:literal, $($call:tt)*) => {
+    match $n {
+      0 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 0, $op2>::$($call)*,
+      1 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 1, $op2>::$($call)*,
+      2 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 2, $op2>::$($call)*,
+      3 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 3, $op2>::$($call)*,
+      4 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 4, $op2>::$($call)*,
+      5 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 5, $op2>::$($call)*,
+      6 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 6, $op2>::$($call)*,
+      7 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 7, $op2>::$($call)*,
+      8 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 8, $op2>::$($call)*,
+      9 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 9, $op2>::$($call)*,
+      10 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 10, $op2>::$($call)*,
+      11 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 11, $op2>::$($call)*,
+      12 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 12, $op2>::$($call)*,
+      13 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 13, $op2>::$($call)*,
+      14 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 14, $op2>::$($call)*,
+      15 => $crate::register::sysreg::SysReg::<$op0, $op1, $crn, 15, $op2>::$($call)*,
+      _ => panic!("system register instance out of range"),
+    }
+  };
+}
+
+/// Helper macro to determine the name of a system register used within the ``mrs`` and ``msr`` assembly
+/// instructions. This is the register name itself or the explicit ``S<op0>_<op1>_C<n>_C<m>_<op2>`` encoding
+/// if one is given.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! system_register_name {
+  ($name:ident) => {
+    stringify!($name)
+  };
+  ($name:ident, $sysreg:ident) => {
+    stringify!($sysreg)
+  };
+}
+
 /// Macro to define an Aarch64 system register and its fields
 ///
 /// # Examples
@@ -72,11 +139,25 @@ macro_rules! impl_system_register_rw {
 ///         foo::BAR::VAL1 | foo::BAZ::VAL2
 ///     );
 /// # }
+/// ```
 ///
+/// Registers that does not have an assembler mnemonic, like *implementation defined* ones, could be defined using
+/// their ``S<op0>_<op1>_C<n>_C<m>_<op2>`` encoding. The register module will still be named as given.
+/// ```no_run
+/// # use ruspiro_arch_aarch64::*;
+///
+/// define_aarch64_register!(
+///     /// Implementation defined register with the encoding op0=3, op1=1, CRn=15, CRm=2, op2=1
+///     bar<u64> ENCODING(S3_1_C15_C2_1) {
+///         /// Some register field
+///         FOO OFFSET(6)
+///     }
+/// );
+/// ```
 #[macro_export]
 macro_rules! define_aarch64_register {
     (@$(#[doc = $rdoc:expr])*
-      $name:ident<$t:ty> {
+      $name:ident<$t:ty> $(ENCODING($sysreg:ident))? {
         $($(#[doc = $fdoc:expr])* $field:ident OFFSET($offset:expr) $(BITS($bits:expr))? $([
             $($(#[doc = $fvdoc:expr])* $enum:ident = $value:expr),*
         ])?),*
@@ -110,13 +191,14 @@ macro_rules! define_aarch64_register {
         )*
 
         /// Read the raw register contents using the appropriate assembly
+        #[cfg(target_arch = "aarch64")]
         #[inline]
         #[allow(dead_code)]
         pub fn get() -> $t {
             let raw_value: $t;
             unsafe {
                 core::arch::asm!(
-                    concat!("mrs {0:x}, ", stringify!($name)),
+                    concat!("mrs {0:x}, ", $crate::system_register_name!($name $(, $sysreg)?)),
                     out(reg) raw_value
                 )
             };
@@ -124,12 +206,13 @@ macro_rules! define_aarch64_register {
         }
 
         /// Write the raw register contents using the appropriate contents
+        #[cfg(target_arch = "aarch64")]
         #[inline]
         #[allow(dead_code)]
         pub fn set(raw_value: $t) {
             unsafe {
                 core::arch::asm!(
-                    concat!("msr ", stringify!($name) , ", {0:x} "),
+                    concat!("msr ", $crate::system_register_name!($name $(, $sysreg)?), ", {0:x} "),
                     in(reg) raw_value
                 )
             }
@@ -140,7 +223,7 @@ macro_rules! define_aarch64_register {
     };
 
     ($(#[doc = $rdoc:expr])*
-     $name:ident<$t:ty> {
+     $name:ident<$t:ty> $(ENCODING($sysreg:ident))? {
         $($(#[doc = $fdoc:expr])*
         $field:ident OFFSET($offset:expr) $(BITS($bits:expr))? $([
             $($(#[doc = $fvdoc:expr])* $enum:ident = $value:expr),*
@@ -154,7 +237,7 @@ macro_rules! define_aarch64_register {
             use $crate::{*, register::*};
             $crate::define_aarch64_register!{
                 @$(#[doc = $rdoc])?
-                $name<$t> {
+                $name<$t> $(ENCODING($sysreg))? {
                     $($(#[doc = $fdoc])* $field OFFSET($offset) $(BITS($bits))? $([
                         $(
                             $(#[doc = $fvdoc])*
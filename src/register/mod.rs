@@ -12,9 +12,25 @@
 use ruspiro_register::{RegisterField, RegisterFieldValue};
 
 pub mod currentel;
+pub mod daif;
+pub mod dit;
 pub mod el0;
 pub mod el1;
 pub mod el2;
 pub mod el3;
+pub mod fpcr;
+pub mod fpsr;
+pub mod nzcv;
+pub mod pan;
+pub mod spsel;
+pub mod ssbs;
+pub mod sysreg;
+pub mod tco;
+pub mod uao;
+
+#[cfg(feature = "cortex-a53")]
+pub mod cortex_a53;
+#[cfg(feature = "cortex-a72")]
+pub mod cortex_a72;
 
 mod macros;
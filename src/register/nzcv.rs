@@ -0,0 +1,32 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # NZCV - Condition Flags
+//!
+//! Allows access to the condition flags of the current PSTATE.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @nzcv<u64> {
+        /// Overflow condition flag
+        V OFFSET(28),
+        /// Carry condition flag
+        C OFFSET(29),
+        /// Zero condition flag
+        Z OFFSET(30),
+        /// Negative condition flag
+        N OFFSET(31)
+    }
+}
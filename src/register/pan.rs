@@ -0,0 +1,32 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # PAN - Privileged Access Never
+//!
+//! Allows access to the Privileged Access Never bit of the current PSTATE. This requires ARMv8.1 or higher.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @pan<u64> ENCODING(S3_0_C4_C2_3) {
+        /// Privileged Access Never
+        PAN OFFSET(22) [
+            /// Privileged data accesses from EL1 or EL2 to virtual addresses accessible at EL0 are allowed
+            DISABLE = 0b0,
+            /// Privileged data accesses from EL1 or EL2 to virtual addresses accessible at EL0 generate a
+            /// permission fault
+            ENABLE = 0b1
+        ]
+    }
+}
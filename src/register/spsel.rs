@@ -0,0 +1,31 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SPSel - Stack Pointer Select
+//!
+//! Allows the selection of the stack pointer used at the current exception level.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @spsel<u64> {
+        /// Stack pointer to use
+        SP OFFSET(0) [
+            /// Use SP_EL0 at all exception levels
+            EL0 = 0b0,
+            /// Use SP_ELx for exception level ELx
+            ELX = 0b1
+        ]
+    }
+}
@@ -0,0 +1,31 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SSBS - Speculative Store Bypass Safe
+//!
+//! Allows access to the Speculative Store Bypass Safe bit of the current PSTATE. This requires ARMv8.5 or higher.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @ssbs<u64> ENCODING(S3_3_C4_C2_6) {
+        /// Speculative Store Bypass Safe
+        SSBS OFFSET(12) [
+            /// Hardware is not permitted to use speculative loads that bypass earlier stores
+            DISABLE = 0b0,
+            /// Hardware is permitted to use speculative loads that bypass earlier stores
+            ENABLE = 0b1
+        ]
+    }
+}
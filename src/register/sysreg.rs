@@ -0,0 +1,169 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Generic System Register Access
+//!
+//! Access any system register by its encoding (op0, op1, CRn, CRm, op2). This is usefull for *implementation defined*
+//! registers that does not have an assembler mnemonic or for a hypervisor that need to emulate register accesses it has
+//! trapped.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::register::sysreg::*;
+//! // CPUECTLR_EL1 of the Cortex-A53 has the encoding S3_1_C15_C2_1
+//! type Cpuectlr = SysReg<3, 1, 15, 2, 1>;
+//!
+//! let value = Cpuectlr::get();
+//! Cpuectlr::set(value | (1 << 6));
+//!
+//! // the encoding decoded from a trapped MSR/MRS access could be compared with the register
+//! # let iss = 0;
+//! if SysRegEncoding::from_iss(iss) == Cpuectlr::ENCODING {
+//!     /* emulate the access */
+//! }
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::register::*;
+
+/// The encoding of a system register as used with the ``MSR`` and ``MRS`` instructions
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SysRegEncoding {
+  pub op0: u8,
+  pub op1: u8,
+  pub crn: u8,
+  pub crm: u8,
+  pub op2: u8,
+}
+
+impl SysRegEncoding {
+  /// Create a new system register encoding
+  pub const fn new(op0: u8, op1: u8, crn: u8, crm: u8, op2: u8) -> Self {
+    Self {
+      op0,
+      op1,
+      crn,
+      crm,
+      op2,
+    }
+  }
+
+  /// Decode the system register encoding from the ISS of an ``ESR_ELx`` value of a trapped MSR/MRS instruction
+  /// (exception class 0x18). The ISS is expected to be the raw value of the ``ESR_ELx::ISS`` field.
+  pub const fn from_iss(iss: u32) -> Self {
+    Self {
+      op0: ((iss >> 20) & 0b11) as u8,
+      op1: ((iss >> 14) & 0b111) as u8,
+      crn: ((iss >> 10) & 0b1111) as u8,
+      crm: ((iss >> 1) & 0b1111) as u8,
+      op2: ((iss >> 17) & 0b111) as u8,
+    }
+  }
+
+  /// Encode this system register into the corresponding bits of the ISS of an ``ESR_ELx`` value of a trapped MSR/MRS
+  /// instruction. The fields Rt and Direction are left 0.
+  pub const fn to_iss(&self) -> u32 {
+    ((self.op0 as u32 & 0b11) << 20)
+      | ((self.op2 as u32 & 0b111) << 17)
+      | ((self.op1 as u32 & 0b111) << 14)
+      | ((self.crn as u32 & 0b1111) << 10)
+      | ((self.crm as u32 & 0b1111) << 1)
+  }
+}
+
+/// A system register identified by its encoding. The register is accessed using the ``S<op0>_<op1>_C<n>_C<m>_<op2>``
+/// form of the ``MSR`` and ``MRS`` instructions.
+pub struct SysReg<const OP0: u8, const OP1: u8, const CRN: u8, const CRM: u8, const OP2: u8>;
+
+impl<const OP0: u8, const OP1: u8, const CRN: u8, const CRM: u8, const OP2: u8>
+  SysReg<OP0, OP1, CRN, CRM, OP2>
+{
+  /// The encoding of this system register
+  pub const ENCODING: SysRegEncoding = SysRegEncoding::new(OP0, OP1, CRN, CRM, OP2);
+
+  /// Read the raw register contents
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  #[allow(dead_code)]
+  pub fn get() -> u64 {
+    let raw_value: u64;
+    unsafe {
+      core::arch::asm!(
+        "mrs {0}, S{op0}_{op1}_C{crn}_C{crm}_{op2}",
+        out(reg) raw_value,
+        op0 = const OP0,
+        op1 = const OP1,
+        crn = const CRN,
+        crm = const CRM,
+        op2 = const OP2,
+      )
+    };
+    raw_value
+  }
+
+  /// Write the raw register contents
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  #[allow(dead_code)]
+  pub fn set(raw_value: u64) {
+    unsafe {
+      core::arch::asm!(
+        "msr S{op0}_{op1}_C{crn}_C{crm}_{op2}, {0}",
+        in(reg) raw_value,
+        op0 = const OP0,
+        op1 = const OP1,
+        crn = const CRN,
+        crm = const CRM,
+        op2 = const OP2,
+      )
+    };
+  }
+
+  /// Update the contents of the register from the ``RegisterFieldValue`` given. This will only change the bits the
+  /// ``RegisterField`` definition specifies.
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  #[allow(dead_code)]
+  pub fn write(field_value: RegisterFieldValue<u64>) {
+    let raw_value = (Self::get() & !field_value.mask()) | field_value.raw_value();
+    Self::set(raw_value);
+  }
+
+  /// Read the contents of a specific ``RegisterField``. The returned value is already shifted to the right to start
+  /// at bit 0.
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  #[allow(dead_code)]
+  pub fn read(field: RegisterField<u64>) -> RegisterFieldValue<u64> {
+    let raw_value = Self::get() & field.mask();
+    RegisterFieldValue::<u64>::new(field, raw_value >> field.shift())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn iss_encoding() {
+    // ACTLR_EL1
+    let actlr = SysRegEncoding::new(3, 0, 1, 0, 1);
+    assert_eq!(actlr.to_iss(), 0x32_0400);
+    assert_eq!(SysRegEncoding::from_iss(0x32_0400), actlr);
+  }
+
+  #[test]
+  fn iss_round_trip_ignores_rt_and_direction() {
+    let encoding = SysRegEncoding::new(3, 7, 15, 15, 7);
+    assert_eq!(encoding.to_iss(), 0x3F_FC1E);
+    assert_eq!(
+      SysRegEncoding::from_iss(encoding.to_iss() | 0b1_1111 << 5 | 1),
+      encoding
+    );
+    let encoding = SysRegEncoding::new(2, 3, 0, 5, 4);
+    assert_eq!(SysRegEncoding::from_iss(encoding.to_iss()), encoding);
+  }
+}
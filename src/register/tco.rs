@@ -0,0 +1,32 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # TCO - Tag Check Override
+//!
+//! Allows access to the Tag Check Override bit of the current PSTATE. This requires ARMv8.5 with the Memory Tagging
+//! Extension.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//! R/W | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @tco<u64> ENCODING(S3_3_C4_C2_7) {
+        /// Tag Check Override
+        TCO OFFSET(25) [
+            /// Loads and Stores are not affected by this control
+            DISABLE = 0b0,
+            /// Loads and Stores are unchecked
+            ENABLE = 0b1
+        ]
+    }
+}
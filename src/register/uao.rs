@@ -0,0 +1,31 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # UAO - User Access Override
+//!
+//! Allows access to the User Access Override bit of the current PSTATE. This requires ARMv8.2 or higher.
+//!
+//! ## Usage Constraints
+//! EL0 | EL1 (NS) | EL1(S) | EL2 | EL3(NS) | EL3(S)
+//! ----|----------|--------|-----|---------|-------
+//!  -  | R/W      | R/W    | R/W | R/W     | R/W
+//!
+
+use crate::register::*;
+use crate::{define_aarch64_register, impl_system_register_rw};
+
+define_aarch64_register! {
+    @uao<u64> ENCODING(S3_0_C4_C2_4) {
+        /// User Access Override
+        UAO OFFSET(23) [
+            /// The behavior of LDTR* and STTR* instructions is as defined in the base ARMv8 architecture
+            DISABLE = 0b0,
+            /// LDTR* and STTR* instructions behave as the equivalent LDR* and STR* instructions
+            ENABLE = 0b1
+        ]
+    }
+}
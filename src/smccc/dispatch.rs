@@ -0,0 +1,310 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # EL3 SMC Dispatcher
+//!
+//! Minimal secure monitor runtime for boards booting at EL3 without any trusted firmware. The EL3 synchronous
+//! exception handler calls ``PsciDispatcher::dispatch`` with the saved register state for each ``SMC`` taken from a
+//! lower exception level (``exception::ec::SMC64``). The standard PSCI calls are routed to the ``PsciPlatform``
+//! callbacks provided by the board, any other call returns ``NOT_SUPPORTED``. The board advertises the callbacks it
+//! implements with ``PsciPlatform::features``.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::exception::TrapFrame;
+//! # use ruspiro_arch_aarch64::smccc::{dispatch::*, psci::{function, PsciError}, FunctionId};
+//! struct Board;
+//!
+//! impl PsciPlatform for Board {
+//!     fn features(&self, function: FunctionId) -> Result<u32, PsciError> {
+//!         match function {
+//!             function::CPU_ON_32 | function::CPU_ON_64 => Ok(0),
+//!             _ => Err(PsciError::NotSupported),
+//!         }
+//!     }
+//!
+//!     fn cpu_on(&self, target_cpu: u64, entry_point: u64, context_id: u64) -> Result<(), PsciError> {
+//!         /* release the core from the spin-table */
+//!         Ok(())
+//!     }
+//! }
+//!
+//! static DISPATCHER: PsciDispatcher<Board> = PsciDispatcher::new(Board);
+//!
+//! // within the EL3 synchronous exception handler
+//! # let mut frame = TrapFrame::new();
+//! DISPATCHER.dispatch(&mut frame);
+//! ```
+//!
+//! The preferred return address of an ``SMC`` is the next instruction, so the ``ELR_EL3`` value is not touched.
+
+use super::{
+  psci::{function, AffinityState, PsciError, PsciVersion},
+  Convention, FunctionId, NOT_SUPPORTED,
+};
+use crate::exception::TrapFrame;
+
+/// The PSCI version implemented by the dispatcher
+pub const PSCI_VERSION: PsciVersion = PsciVersion { major: 1, minor: 1 };
+
+/// The board specific implementation of the PSCI calls. Any call not implemented returns ``NotSupported``.
+pub trait PsciPlatform {
+  /// Query whether the given PSCI function is implemented by the platform and return its feature flags. Each function
+  /// with an implemented callback need to be advertised here, the default advertises none. ``PSCI_VERSION`` and
+  /// ``PSCI_FEATURES`` are always advertised by the dispatcher itself.
+  fn features(&self, _function: FunctionId) -> Result<u32, PsciError> {
+    Err(PsciError::NotSupported)
+  }
+
+  /// Suspend the current core
+  fn cpu_suspend(
+    &self,
+    _power_state: u32,
+    _entry_point: u64,
+    _context_id: u64,
+  ) -> Result<(), PsciError> {
+    Err(PsciError::NotSupported)
+  }
+
+  /// Power down the current core. This does not return on success
+  fn cpu_off(&self) -> PsciError {
+    PsciError::NotSupported
+  }
+
+  /// Power up the core with the MPIDR affinity ``target_cpu`` to start execution at ``entry_point`` with
+  /// ``context_id`` in x0
+  fn cpu_on(&self, _target_cpu: u64, _entry_point: u64, _context_id: u64) -> Result<(), PsciError> {
+    Err(PsciError::NotSupported)
+  }
+
+  /// Get the power state of the given affinity instance
+  fn affinity_info(
+    &self,
+    _target_affinity: u64,
+    _lowest_affinity_level: u32,
+  ) -> Result<AffinityState, PsciError> {
+    Err(PsciError::NotSupported)
+  }
+
+  /// Power off the system. This does not return on success
+  fn system_off(&self) -> PsciError {
+    PsciError::NotSupported
+  }
+
+  /// Reset the system. This does not return on success
+  fn system_reset(&self) -> PsciError {
+    PsciError::NotSupported
+  }
+
+  /// Reset the system with the given type. This does not return on success
+  fn system_reset2(&self, _reset_type: u32, _cookie: u64) -> PsciError {
+    PsciError::NotSupported
+  }
+}
+
+/// Dispatches the SMC calls to the ``PsciPlatform``
+pub struct PsciDispatcher<P: PsciPlatform> {
+  platform: P,
+}
+
+impl<P: PsciPlatform> PsciDispatcher<P> {
+  /// Create the dispatcher routing PSCI calls to the given platform
+  pub const fn new(platform: P) -> Self {
+    Self { platform }
+  }
+
+  /// Handle the SMC call stored in the trap frame. The function ID is taken from w0 and the arguments from x1-x3.
+  /// The result is written back to x0.
+  pub fn dispatch(&self, frame: &mut TrapFrame) {
+    let function = FunctionId(frame.x[0] as u32);
+    let arg = |num: usize| match function.convention() {
+      Convention::Smc32 => frame.x[num] & 0xFFFF_FFFF,
+      Convention::Smc64 => frame.x[num],
+    };
+    let (arg1, arg2, arg3) = (arg(1), arg(2), arg(3));
+
+    let result: i32 = match function {
+      function::PSCI_VERSION => PSCI_VERSION.to_raw() as i32,
+      function::PSCI_FEATURES => match FunctionId(arg1 as u32) {
+        function::PSCI_VERSION | function::PSCI_FEATURES => 0,
+        queried => Self::to_return(self.platform.features(queried)),
+      },
+      function::CPU_SUSPEND_32 | function::CPU_SUSPEND_64 => Self::to_return(
+        self
+          .platform
+          .cpu_suspend(arg1 as u32, arg2, arg3)
+          .map(|_| 0),
+      ),
+      function::CPU_OFF => self.platform.cpu_off().to_return(),
+      function::CPU_ON_32 | function::CPU_ON_64 => {
+        Self::to_return(self.platform.cpu_on(arg1, arg2, arg3).map(|_| 0))
+      }
+      function::AFFINITY_INFO_32 | function::AFFINITY_INFO_64 => Self::to_return(
+        self
+          .platform
+          .affinity_info(arg1, arg2 as u32)
+          .map(|state| state as u32),
+      ),
+      function::SYSTEM_OFF => self.platform.system_off().to_return(),
+      function::SYSTEM_RESET => self.platform.system_reset().to_return(),
+      function::SYSTEM_RESET2_32 | function::SYSTEM_RESET2_64 => {
+        self.platform.system_reset2(arg1 as u32, arg2).to_return()
+      }
+      _ => NOT_SUPPORTED,
+    };
+
+    frame.x[0] = match function.convention() {
+      Convention::Smc32 => result as u32 as u64,
+      Convention::Smc64 => result as i64 as u64,
+    };
+  }
+
+  fn to_return(result: Result<u32, PsciError>) -> i32 {
+    match result {
+      Ok(value) => value as i32,
+      Err(error) => error.to_return(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use core::cell::Cell;
+
+  /// Board only implementing ``CPU_ON`` and recording its arguments
+  struct Board {
+    cpu_on: Cell<Option<(u64, u64, u64)>>,
+  }
+
+  impl PsciPlatform for Board {
+    fn features(&self, function: FunctionId) -> Result<u32, PsciError> {
+      match function {
+        function::CPU_ON_32 | function::CPU_ON_64 => Ok(0),
+        _ => Err(PsciError::NotSupported),
+      }
+    }
+
+    fn cpu_on(&self, target_cpu: u64, entry_point: u64, context_id: u64) -> Result<(), PsciError> {
+      self.cpu_on.set(Some((target_cpu, entry_point, context_id)));
+      Ok(())
+    }
+  }
+
+  struct DefaultBoard;
+
+  impl PsciPlatform for DefaultBoard {}
+
+  fn smc<P: PsciPlatform>(
+    dispatcher: &PsciDispatcher<P>,
+    function: FunctionId,
+    args: [u64; 3],
+  ) -> u64 {
+    let mut frame = TrapFrame::new();
+    frame.x[0] = function.0 as u64;
+    frame.x[1..4].copy_from_slice(&args);
+    dispatcher.dispatch(&mut frame);
+    frame.x[0]
+  }
+
+  #[test]
+  fn version_and_features_are_always_advertised() {
+    let dispatcher = PsciDispatcher::new(DefaultBoard);
+    assert_eq!(
+      smc(&dispatcher, function::PSCI_VERSION, [0; 3]),
+      0x0001_0001
+    );
+    for queried in [function::PSCI_VERSION, function::PSCI_FEATURES] {
+      assert_eq!(
+        smc(
+          &dispatcher,
+          function::PSCI_FEATURES,
+          [queried.0 as u64, 0, 0]
+        ),
+        0
+      );
+    }
+  }
+
+  #[test]
+  fn default_platform_advertises_no_callback() {
+    let dispatcher = PsciDispatcher::new(DefaultBoard);
+    for queried in [
+      function::CPU_ON_64,
+      function::CPU_SUSPEND_64,
+      function::AFFINITY_INFO_64,
+      function::SYSTEM_OFF,
+      function::SYSTEM_RESET,
+      function::SYSTEM_RESET2_64,
+    ] {
+      assert_eq!(
+        smc(
+          &dispatcher,
+          function::PSCI_FEATURES,
+          [queried.0 as u64, 0, 0]
+        ),
+        0xFFFF_FFFF
+      );
+    }
+    assert_eq!(smc(&dispatcher, function::SYSTEM_OFF, [0; 3]), 0xFFFF_FFFF);
+  }
+
+  #[test]
+  fn cpu_on_is_routed_to_the_platform() {
+    let dispatcher = PsciDispatcher::new(Board {
+      cpu_on: Cell::new(None),
+    });
+    assert_eq!(
+      smc(
+        &dispatcher,
+        function::PSCI_FEATURES,
+        [function::CPU_ON_64.0 as u64, 0, 0]
+      ),
+      0
+    );
+    assert_eq!(
+      smc(
+        &dispatcher,
+        function::CPU_ON_64,
+        [0x1_0000_0001, 0x8_0000, 42]
+      ),
+      0
+    );
+    assert_eq!(
+      dispatcher.platform.cpu_on.get(),
+      Some((0x1_0000_0001, 0x8_0000, 42))
+    );
+  }
+
+  #[test]
+  fn smc32_arguments_are_truncated() {
+    let dispatcher = PsciDispatcher::new(Board {
+      cpu_on: Cell::new(None),
+    });
+    assert_eq!(
+      smc(
+        &dispatcher,
+        function::CPU_ON_32,
+        [0xFFFF_FFFF_0000_0001, 0x1_8000_0000, 42]
+      ),
+      0
+    );
+    assert_eq!(
+      dispatcher.platform.cpu_on.get(),
+      Some((0x1, 0x8000_0000, 42))
+    );
+  }
+
+  #[test]
+  fn unknown_function_is_not_supported() {
+    let dispatcher = PsciDispatcher::new(DefaultBoard);
+    assert_eq!(
+      smc(&dispatcher, FunctionId(0x8400_00FF), [0; 3]),
+      0xFFFF_FFFF
+    );
+    assert_eq!(smc(&dispatcher, FunctionId(0xC400_00FF), [0; 3]), u64::MAX);
+  }
+}
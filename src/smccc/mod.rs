@@ -0,0 +1,195 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # SMC Calling Convention
+//!
+//! Calls into secure firmware or a hypervisor following the Arm SMC Calling Convention (SMCCC). The function ID is
+//! passed in x0 and up to 7 arguments in x1-x7. The results are returned in x0-x17, while most of the functions only
+//! use x0-x3. The ``Conduit`` defines whether the call is issued with the ``SMC`` or the ``HVC`` instruction. Typed
+//! services like ``psci`` are build on top of it.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::smccc::*;
+//! // SMCCC_VERSION
+//! let function = FunctionId::new(CallType::Fast, Convention::Smc32, OwningEntity::ARM, 0x0000);
+//! let result = Smc.call(function, &[0; 7]);
+//! let version = result[0] as u32;
+//! ```
+
+#[cfg(feature = "psci-dispatcher")]
+pub mod dispatch;
+pub mod psci;
+
+/// Type of the call
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CallType {
+  /// Yielding call that could be preempted
+  Yielding = 0,
+  /// Fast call that executes atomically
+  Fast = 1,
+}
+
+/// Calling convention used for the arguments and results
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Convention {
+  /// Only the lower 32 bits of the register are used for arguments and results
+  Smc32 = 0,
+  /// The full 64 bits of the register are used for arguments and results
+  Smc64 = 1,
+}
+
+/// The entity owning a service call range
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OwningEntity(pub u8);
+
+impl OwningEntity {
+  /// Arm Architecture Calls
+  pub const ARM: Self = Self(0);
+  /// CPU Service Calls
+  pub const CPU: Self = Self(1);
+  /// SiP Service Calls
+  pub const SIP: Self = Self(2);
+  /// OEM Service Calls
+  pub const OEM: Self = Self(3);
+  /// Standard Secure Service Calls, like PSCI
+  pub const STANDARD_SECURE: Self = Self(4);
+  /// Standard Hypervisor Service Calls
+  pub const STANDARD_HYPERVISOR: Self = Self(5);
+  /// Vendor Specific Hypervisor Service Calls
+  pub const VENDOR_HYPERVISOR: Self = Self(6);
+}
+
+/// The function identifier of a call passed in w0
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FunctionId(pub u32);
+
+impl FunctionId {
+  /// Build the function identifier from it's parts
+  pub const fn new(
+    call_type: CallType,
+    convention: Convention,
+    owner: OwningEntity,
+    number: u16,
+  ) -> Self {
+    Self(
+      (call_type as u32) << 31
+        | (convention as u32) << 30
+        | ((owner.0 as u32) & 0x3F) << 24
+        | number as u32,
+    )
+  }
+
+  /// The type of the call
+  pub const fn call_type(&self) -> CallType {
+    if self.0 & (1 << 31) != 0 {
+      CallType::Fast
+    } else {
+      CallType::Yielding
+    }
+  }
+
+  /// The calling convention of the call
+  pub const fn convention(&self) -> Convention {
+    if self.0 & (1 << 30) != 0 {
+      Convention::Smc64
+    } else {
+      Convention::Smc32
+    }
+  }
+
+  /// The entity owning this call
+  pub const fn owner(&self) -> OwningEntity {
+    OwningEntity(((self.0 >> 24) & 0x3F) as u8)
+  }
+
+  /// The function number within the owning entity
+  pub const fn number(&self) -> u16 {
+    self.0 as u16
+  }
+}
+
+/// Return value of a call with an unknown function ID
+pub const NOT_SUPPORTED: i32 = -1;
+
+/// The conduit used to issue calls
+pub trait Conduit {
+  /// Issue the call of ``function`` with the arguments ``args`` passed in x1-x7. Returns the contents of x0-x17
+  /// after the call. Arguments not used by the function should be 0.
+  fn call(&self, function: FunctionId, args: &[u64; 7]) -> [u64; 18];
+}
+
+/// Issue calls using the ``SMC`` instruction to the secure monitor at EL3
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Smc;
+
+#[cfg(target_arch = "aarch64")]
+impl Conduit for Smc {
+  #[inline]
+  fn call(&self, function: FunctionId, args: &[u64; 7]) -> [u64; 18] {
+    let mut result = [0u64; 18];
+    unsafe {
+      core::arch::asm!(
+        "smc #0",
+        inout("x0") function.0 as u64 => result[0],
+        inout("x1") args[0] => result[1],
+        inout("x2") args[1] => result[2],
+        inout("x3") args[2] => result[3],
+        inout("x4") args[3] => result[4],
+        inout("x5") args[4] => result[5],
+        inout("x6") args[5] => result[6],
+        inout("x7") args[6] => result[7],
+        lateout("x8") result[8],
+        lateout("x9") result[9],
+        lateout("x10") result[10],
+        lateout("x11") result[11],
+        lateout("x12") result[12],
+        lateout("x13") result[13],
+        lateout("x14") result[14],
+        lateout("x15") result[15],
+        lateout("x16") result[16],
+        lateout("x17") result[17],
+      )
+    };
+    result
+  }
+}
+
+/// Issue calls using the ``HVC`` instruction to the hypervisor at EL2
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Hvc;
+
+#[cfg(target_arch = "aarch64")]
+impl Conduit for Hvc {
+  #[inline]
+  fn call(&self, function: FunctionId, args: &[u64; 7]) -> [u64; 18] {
+    let mut result = [0u64; 18];
+    unsafe {
+      core::arch::asm!(
+        "hvc #0",
+        inout("x0") function.0 as u64 => result[0],
+        inout("x1") args[0] => result[1],
+        inout("x2") args[1] => result[2],
+        inout("x3") args[2] => result[3],
+        inout("x4") args[3] => result[4],
+        inout("x5") args[4] => result[5],
+        inout("x6") args[5] => result[6],
+        inout("x7") args[6] => result[7],
+        lateout("x8") result[8],
+        lateout("x9") result[9],
+        lateout("x10") result[10],
+        lateout("x11") result[11],
+        lateout("x12") result[12],
+        lateout("x13") result[13],
+        lateout("x14") result[14],
+        lateout("x15") result[15],
+        lateout("x16") result[16],
+        lateout("x17") result[17],
+      )
+    };
+    result
+  }
+}
@@ -0,0 +1,370 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Power State Coordination Interface
+//!
+//! Typed client of the PSCI firmware interface to power on and off cores and to reset or power off the whole system.
+//! The calls are issued through a ``Conduit``, which allows to verify the client against a fake firmware on the host.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::smccc::{Smc, psci::*};
+//! extern "C" {
+//!     fn _secondary_entry();
+//! }
+//!
+//! let psci = Psci::new(Smc);
+//! // power on core 1 of cluster 0
+//! psci.cpu_on(0x1, _secondary_entry as usize as u64, 0).expect("core 1 failed to power on");
+//! ```
+
+use super::{Conduit, Convention, FunctionId};
+
+/// The function IDs of the PSCI calls
+pub mod function {
+  use super::FunctionId;
+
+  /// Get the version of the PSCI implementation
+  pub const PSCI_VERSION: FunctionId = FunctionId(0x8400_0000);
+  /// Suspend the execution of the current core, SMC32 convention
+  pub const CPU_SUSPEND_32: FunctionId = FunctionId(0x8400_0001);
+  /// Suspend the execution of the current core, SMC64 convention
+  pub const CPU_SUSPEND_64: FunctionId = FunctionId(0xC400_0001);
+  /// Power down the current core
+  pub const CPU_OFF: FunctionId = FunctionId(0x8400_0002);
+  /// Power up a core, SMC32 convention
+  pub const CPU_ON_32: FunctionId = FunctionId(0x8400_0003);
+  /// Power up a core, SMC64 convention
+  pub const CPU_ON_64: FunctionId = FunctionId(0xC400_0003);
+  /// Get the power state of an affinity instance, SMC32 convention
+  pub const AFFINITY_INFO_32: FunctionId = FunctionId(0x8400_0004);
+  /// Get the power state of an affinity instance, SMC64 convention
+  pub const AFFINITY_INFO_64: FunctionId = FunctionId(0xC400_0004);
+  /// Migrate a uniprocessor trusted OS to another core, SMC32 convention
+  pub const MIGRATE_32: FunctionId = FunctionId(0x8400_0005);
+  /// Migrate a uniprocessor trusted OS to another core, SMC64 convention
+  pub const MIGRATE_64: FunctionId = FunctionId(0xC400_0005);
+  /// Get the multicore support of the trusted OS
+  pub const MIGRATE_INFO_TYPE: FunctionId = FunctionId(0x8400_0006);
+  /// Get the core a uniprocessor trusted OS resides on, SMC32 convention
+  pub const MIGRATE_INFO_UP_CPU_32: FunctionId = FunctionId(0x8400_0007);
+  /// Get the core a uniprocessor trusted OS resides on, SMC64 convention
+  pub const MIGRATE_INFO_UP_CPU_64: FunctionId = FunctionId(0xC400_0007);
+  /// Power off the whole system
+  pub const SYSTEM_OFF: FunctionId = FunctionId(0x8400_0008);
+  /// Cold reset the whole system
+  pub const SYSTEM_RESET: FunctionId = FunctionId(0x8400_0009);
+  /// Query whether a PSCI function is implemented
+  pub const PSCI_FEATURES: FunctionId = FunctionId(0x8400_000A);
+  /// Reset the whole system with an architectural or vendor specific reset type, SMC32 convention
+  pub const SYSTEM_RESET2_32: FunctionId = FunctionId(0x8400_0012);
+  /// Reset the whole system with an architectural or vendor specific reset type, SMC64 convention
+  pub const SYSTEM_RESET2_64: FunctionId = FunctionId(0xC400_0012);
+}
+
+/// The error codes returned by PSCI calls
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PsciError {
+  /// The function is not implemented
+  NotSupported = -1,
+  /// The arguments passed are invalid
+  InvalidParameters = -2,
+  /// The call is not permitted in the current state of the system
+  Denied = -3,
+  /// The target core is already powered on
+  AlreadyOn = -4,
+  /// A power on of the target core is already in progress
+  OnPending = -5,
+  /// The firmware failed to execute the call
+  InternalFailure = -6,
+  /// The trusted OS is not present on the target core
+  NotPresent = -7,
+  /// The target core is disabled
+  Disabled = -8,
+  /// The entry point address is invalid
+  InvalidAddress = -9,
+}
+
+impl PsciError {
+  /// Convert the raw return value of a PSCI call into a result. Any non negative value is a success.
+  pub const fn from_return(value: i32) -> Result<u32, PsciError> {
+    match value {
+      -1 => Err(PsciError::NotSupported),
+      -2 => Err(PsciError::InvalidParameters),
+      -3 => Err(PsciError::Denied),
+      -4 => Err(PsciError::AlreadyOn),
+      -5 => Err(PsciError::OnPending),
+      -6 => Err(PsciError::InternalFailure),
+      -7 => Err(PsciError::NotPresent),
+      -8 => Err(PsciError::Disabled),
+      -9 => Err(PsciError::InvalidAddress),
+      v if v < 0 => Err(PsciError::InternalFailure),
+      v => Ok(v as u32),
+    }
+  }
+
+  /// The raw return value of this error as passed in w0
+  pub const fn to_return(self) -> i32 {
+    self as i32
+  }
+}
+
+/// The version of the PSCI implementation
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PsciVersion {
+  /// Major version
+  pub major: u16,
+  /// Minor version
+  pub minor: u16,
+}
+
+impl PsciVersion {
+  /// Decode the version as returned by ``PSCI_VERSION``
+  pub const fn from_raw(raw: u32) -> Self {
+    Self {
+      major: (raw >> 16) as u16,
+      minor: raw as u16,
+    }
+  }
+
+  /// Encode the version as returned by ``PSCI_VERSION``
+  pub const fn to_raw(self) -> u32 {
+    (self.major as u32) << 16 | self.minor as u32
+  }
+}
+
+/// The power state of an affinity instance as returned by ``AFFINITY_INFO``
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AffinityState {
+  /// At least one core of the affinity instance is powered on
+  On = 0,
+  /// All cores of the affinity instance are powered off
+  Off = 1,
+  /// The affinity instance is being powered on
+  OnPending = 2,
+}
+
+/// The PSCI client issuing the calls through the conduit ``C``
+pub struct Psci<C: Conduit> {
+  conduit: C,
+}
+
+impl<C: Conduit> Psci<C> {
+  /// Create the PSCI client using the given conduit
+  pub const fn new(conduit: C) -> Self {
+    Self { conduit }
+  }
+
+  fn call(&self, function: FunctionId, arg1: u64, arg2: u64, arg3: u64) -> i32 {
+    // the upper 32 bits of the arguments are not defined for the SMC32 calling convention
+    let mask = match function.convention() {
+      Convention::Smc32 => 0xFFFF_FFFF,
+      Convention::Smc64 => u64::MAX,
+    };
+    let result = self.conduit.call(
+      function,
+      &[arg1 & mask, arg2 & mask, arg3 & mask, 0, 0, 0, 0],
+    );
+    result[0] as i32
+  }
+
+  /// Get the version of the PSCI implementation
+  pub fn version(&self) -> PsciVersion {
+    PsciVersion::from_raw(self.call(function::PSCI_VERSION, 0, 0, 0) as u32)
+  }
+
+  /// Query whether the given PSCI function is implemented. Returns the feature flags of the function if so.
+  pub fn features(&self, function: FunctionId) -> Result<u32, PsciError> {
+    PsciError::from_return(self.call(function::PSCI_FEATURES, function.0 as u64, 0, 0))
+  }
+
+  /// Suspend the execution of the current core. ``power_state`` is the implementation specific power state to enter.
+  /// If this is a power down state the core resumes at ``entry_point`` with ``context_id`` passed in x0.
+  pub fn cpu_suspend(
+    &self,
+    power_state: u32,
+    entry_point: u64,
+    context_id: u64,
+  ) -> Result<(), PsciError> {
+    PsciError::from_return(self.call(
+      function::CPU_SUSPEND_64,
+      power_state as u64,
+      entry_point,
+      context_id,
+    ))
+    .map(|_| ())
+  }
+
+  /// Power down the current core. This only returns in case of an error.
+  pub fn cpu_off(&self) -> PsciError {
+    match PsciError::from_return(self.call(function::CPU_OFF, 0, 0, 0)) {
+      Err(error) => error,
+      Ok(_) => PsciError::InternalFailure,
+    }
+  }
+
+  /// Power up the core with the MPIDR affinity ``target_cpu``. It starts execution at ``entry_point`` with
+  /// ``context_id`` passed in x0.
+  pub fn cpu_on(
+    &self,
+    target_cpu: u64,
+    entry_point: u64,
+    context_id: u64,
+  ) -> Result<(), PsciError> {
+    PsciError::from_return(self.call(function::CPU_ON_64, target_cpu, entry_point, context_id))
+      .map(|_| ())
+  }
+
+  /// Get the power state of the affinity instance ``target_affinity`` at the ``lowest_affinity_level``
+  pub fn affinity_info(
+    &self,
+    target_affinity: u64,
+    lowest_affinity_level: u32,
+  ) -> Result<AffinityState, PsciError> {
+    match PsciError::from_return(self.call(
+      function::AFFINITY_INFO_64,
+      target_affinity,
+      lowest_affinity_level as u64,
+      0,
+    ))? {
+      0 => Ok(AffinityState::On),
+      1 => Ok(AffinityState::Off),
+      2 => Ok(AffinityState::OnPending),
+      _ => Err(PsciError::InternalFailure),
+    }
+  }
+
+  /// Power off the whole system. This only returns in case of an error.
+  pub fn system_off(&self) -> PsciError {
+    match PsciError::from_return(self.call(function::SYSTEM_OFF, 0, 0, 0)) {
+      Err(error) => error,
+      Ok(_) => PsciError::InternalFailure,
+    }
+  }
+
+  /// Reset the whole system. This only returns in case of an error.
+  pub fn system_reset(&self) -> PsciError {
+    match PsciError::from_return(self.call(function::SYSTEM_RESET, 0, 0, 0)) {
+      Err(error) => error,
+      Ok(_) => PsciError::InternalFailure,
+    }
+  }
+
+  /// Reset the whole system with the given ``reset_type`` and implementation specific ``cookie``. This only returns
+  /// in case of an error.
+  pub fn system_reset2(&self, reset_type: u32, cookie: u64) -> PsciError {
+    match PsciError::from_return(self.call(
+      function::SYSTEM_RESET2_64,
+      reset_type as u64,
+      cookie,
+      0,
+    )) {
+      Err(error) => error,
+      Ok(_) => PsciError::InternalFailure,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use core::cell::Cell;
+
+  /// Firmware recording the last call and answering it with a fixed return value
+  struct FakeFirmware {
+    last_call: Cell<Option<(FunctionId, [u64; 7])>>,
+    ret: u64,
+  }
+
+  impl FakeFirmware {
+    fn returning(ret: i64) -> Self {
+      Self {
+        last_call: Cell::new(None),
+        ret: ret as u64,
+      }
+    }
+  }
+
+  impl Conduit for &FakeFirmware {
+    fn call(&self, function: FunctionId, args: &[u64; 7]) -> [u64; 18] {
+      self.last_call.set(Some((function, *args)));
+      let mut result = [0; 18];
+      result[0] = self.ret;
+      result
+    }
+  }
+
+  #[test]
+  fn version() {
+    let firmware = FakeFirmware::returning(0x0001_0002);
+    let psci = Psci::new(&firmware);
+    assert_eq!(psci.version(), PsciVersion { major: 1, minor: 2 });
+    assert_eq!(
+      firmware.last_call.get(),
+      Some((function::PSCI_VERSION, [0; 7]))
+    );
+  }
+
+  #[test]
+  fn cpu_on_passes_arguments() {
+    let firmware = FakeFirmware::returning(0);
+    let psci = Psci::new(&firmware);
+    assert_eq!(psci.cpu_on(0x1_0000_0102, 0x8_0000, 42), Ok(()));
+    assert_eq!(
+      firmware.last_call.get(),
+      Some((
+        function::CPU_ON_64,
+        [0x1_0000_0102, 0x8_0000, 42, 0, 0, 0, 0]
+      ))
+    );
+  }
+
+  #[test]
+  fn errors_are_decoded() {
+    let firmware = FakeFirmware::returning(-4);
+    assert_eq!(
+      Psci::new(&firmware).cpu_on(0x1, 0x8_0000, 0),
+      Err(PsciError::AlreadyOn)
+    );
+    let firmware = FakeFirmware::returning(-1);
+    assert_eq!(Psci::new(&firmware).system_off(), PsciError::NotSupported);
+    let firmware = FakeFirmware::returning(-42);
+    assert_eq!(
+      Psci::new(&firmware).features(function::SYSTEM_RESET2_64),
+      Err(PsciError::InternalFailure)
+    );
+  }
+
+  #[test]
+  fn affinity_info_states() {
+    let firmware = FakeFirmware::returning(2);
+    assert_eq!(
+      Psci::new(&firmware).affinity_info(0x1, 0),
+      Ok(AffinityState::OnPending)
+    );
+    let firmware = FakeFirmware::returning(3);
+    assert_eq!(
+      Psci::new(&firmware).affinity_info(0x1, 0),
+      Err(PsciError::InternalFailure)
+    );
+  }
+
+  #[test]
+  fn smc32_arguments_are_truncated() {
+    let firmware = FakeFirmware::returning(0);
+    let psci = Psci::new(&firmware);
+    psci.call(
+      function::CPU_ON_32,
+      0xFFFF_FFFF_0000_0001,
+      0x1_8000_0000,
+      0x2,
+    );
+    assert_eq!(
+      firmware.last_call.get(),
+      Some((function::CPU_ON_32, [0x1, 0x8000_0000, 0x2, 0, 0, 0, 0]))
+    );
+  }
+}
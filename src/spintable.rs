@@ -0,0 +1,197 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Spin-Table Secondary Core Release
+//!
+//! Boot loaders like the Raspberry Pi ``armstub`` park the secondary cores in a loop waiting for their release
+//! address being written to the spin-table. Each core has its own 64Bit slot at ``base + 8 * core``, where ``core`` is
+//! the id of the core within its cluster as returned by ``core_index``. Once this slot contains a non zero value the core
+//! jumps to this address.
+//!
+//! ``SpinTable::release_core`` passes the entry function and the stack of the core through a separate boot table and
+//! releases the core to a small trampoline that sets up the stack and calls the entry function with the core index.
+//! ``SpinTable::park_secondary`` implements the waiting loop for secondary cores entering the own code.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::spintable::*;
+//! static mut STACK_CORE1: [u8; 4096] = [0; 4096];
+//!
+//! extern "C" fn secondary_main(core: usize) -> ! {
+//!     loop {}
+//! }
+//!
+//! unsafe {
+//!     SpinTable::RASPBERRY_PI.release_core(
+//!         1,
+//!         secondary_main,
+//!         STACK_CORE1.as_mut_ptr().add(STACK_CORE1.len()),
+//!     );
+//! }
+//! ```
+#![cfg(target_arch = "aarch64")]
+
+use crate::addr::VirtAddr;
+use crate::cache::{clean_dcache_range_poc, clean_invalidate_dcache_range_poc};
+use crate::instructions::{dsb, sev, wfe};
+use crate::register::el1::mpidr_el1::Affinity;
+use core::arch::{asm, global_asm};
+use core::ptr;
+
+/// Maximum number of cores that could be released with a stack
+pub const MAX_CORES: usize = 8;
+
+/// The index of the current core within the spin-table. This is the id of the core within its cluster, taken from
+/// the affinity level 1 if the ``MT`` bit of MPIDR_EL1 is set and from the affinity level 0 otherwise. The boot
+/// trampoline selects the boot data of a released core the same way.
+#[inline]
+pub fn core_index() -> usize {
+  Affinity::current().core() as usize
+}
+
+/// Entry function and stack of a secondary core, read by the boot trampoline
+#[repr(C)]
+struct SecondaryBoot {
+  entry: u64,
+  stack: u64,
+}
+
+#[no_mangle]
+static mut __RUSPIRO_SECONDARY_BOOT: [SecondaryBoot; MAX_CORES] = [
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+  SecondaryBoot { entry: 0, stack: 0 },
+];
+
+extern "C" {
+  fn __ruspiro_secondary_entry();
+}
+
+/// A spin-table with the release address slots starting at a specific base address
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpinTable {
+  base: usize,
+}
+
+impl SpinTable {
+  /// The spin-table used by the Raspberry Pi ``armstub``
+  pub const RASPBERRY_PI: Self = Self::new(0xD8);
+
+  /// Create the spin-table with the given base address of the release address slots
+  pub const fn new(base: usize) -> Self {
+    Self { base }
+  }
+
+  /// The address of the release address slot of a specific core
+  pub const fn release_address(&self, core: usize) -> usize {
+    self.base + 8 * core
+  }
+
+  /// Release the secondary ``core`` from the spin-table. It will call ``entry`` with its core index using the stack
+  /// given with ``stack_top``, which will be aligned to 16 bytes. The boot data and the release address are cleaned
+  /// to the Point of Coherency as the secondary core might run with caches disabled.
+  ///
+  /// The secondary core starts with the MMU disabled, so the addresses of the trampoline, the boot table, ``entry`` and
+  /// ``stack_top`` are used as physical addresses.
+  ///
+  /// # Safety
+  /// The spin-table base need to be valid and the core need to be parked in this spin-table. The stack need to be
+  /// exclusively used by this core. The code and the stack need to be identity mapped, the virtual addresses being
+  /// the physical ones, or the MMU of the current core need to be disabled.
+  ///
+  /// # Panics
+  /// If the core exceeds ``MAX_CORES``
+  pub unsafe fn release_core(
+    &self,
+    core: usize,
+    entry: extern "C" fn(usize) -> !,
+    stack_top: *mut u8,
+  ) {
+    assert!(
+      core < MAX_CORES,
+      "core exceeds the maximum number of supported cores"
+    );
+    let boot = ptr::addr_of_mut!(__RUSPIRO_SECONDARY_BOOT[core]);
+    ptr::write_volatile(
+      boot,
+      SecondaryBoot {
+        entry: entry as usize as u64,
+        stack: (stack_top as u64) & !0xF,
+      },
+    );
+    clean_dcache_range_poc(
+      VirtAddr::from_ptr(boot),
+      core::mem::size_of::<SecondaryBoot>(),
+    );
+
+    let release = self.release_address(core);
+    ptr::write_volatile(
+      release as *mut u64,
+      __ruspiro_secondary_entry as *const () as u64,
+    );
+    clean_dcache_range_poc(VirtAddr::new(release as u64), 8);
+    dsb();
+    sev();
+  }
+
+  /// Park the current secondary core until its release address is written to the spin-table and jump to it. The
+  /// core index is passed in x0. This is the counterpart of ``release_core`` for secondary cores that are not parked by
+  /// the boot loader.
+  ///
+  /// # Safety
+  /// The spin-table base need to be valid and its release address slots need to be cleared to 0 before the
+  /// secondary cores are parked.
+  pub unsafe fn park_secondary(&self) -> ! {
+    let core = core_index();
+    let release = self.release_address(core);
+    loop {
+      // the release address is written to the Point of Coherency, so discard any stale cached value. The line is
+      // cleaned as well as invalidating could drop other dirty data sharing the cache line with the slot
+      clean_invalidate_dcache_range_poc(VirtAddr::new(release as u64), 8);
+      let entry = ptr::read_volatile(release as *const u64);
+      if entry != 0 {
+        asm!("br {entry}", entry = in(reg) entry, in("x0") core, options(noreturn));
+      }
+      wfe();
+    }
+  }
+}
+
+// boot trampoline of a released secondary core: setup the stack from the boot table and call the entry function with
+// the core index, selected by the MT bit like ``core_index``. Cores with an index exceeding the boot table are parked
+// forever.
+global_asm!(
+  ".section .text.__ruspiro_secondary_entry, \"ax\"",
+  ".global __ruspiro_secondary_entry",
+  ".type __ruspiro_secondary_entry, %function",
+  ".balign 4",
+  "__ruspiro_secondary_entry:",
+  "  mrs x0, mpidr_el1",
+  "  tbz x0, #24, 1f",
+  "  lsr x0, x0, #8",
+  "1:",
+  "  and x0, x0, #0xFF",
+  "  cmp x0, #{max_cores}",
+  "  b.hs 2f",
+  "  adrp x1, __RUSPIRO_SECONDARY_BOOT",
+  "  add x1, x1, :lo12:__RUSPIRO_SECONDARY_BOOT",
+  "  add x1, x1, x0, lsl #4",
+  "  ldp x2, x3, [x1]",
+  "  mov sp, x3",
+  "  mov x29, xzr",
+  "  mov x30, xzr",
+  "  br x2",
+  "2:",
+  "  wfe",
+  "  b 2b",
+  ".size __ruspiro_secondary_entry, . - __ruspiro_secondary_entry",
+  max_cores = const MAX_CORES,
+);
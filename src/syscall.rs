@@ -0,0 +1,382 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # EL0 Tasks and System Calls
+//!
+//! ``enter_user`` drops from EL1 into a task running at EL0. The task requests services of the kernel with the
+//! ``SVC`` instruction, which is taken as synchronous exception to EL1. The ``SyscallDispatcher`` decodes the syndrome
+//! of this exception, looks up the system call in a table, keyed either on the immediate of the ``SVC`` instruction
+//! or on the number passed in ``x8``, and writes the result of the handler back into ``x0`` of the trap frame.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::VirtAddr;
+//! # use ruspiro_arch_aarch64::exception::TrapFrame;
+//! # use ruspiro_arch_aarch64::syscall::*;
+//! struct Kernel {
+//!   ticks: u64,
+//! }
+//!
+//! static SYSCALLS: [Syscall<Kernel>; 2] = [
+//!   Syscall { number: 0, handler: |kernel, _| kernel.ticks },
+//!   Syscall { number: 1, handler: |_, args| args.arg(0) + args.arg(1) },
+//! ];
+//!
+//! // within the synchronous exception handler for exceptions taken from EL0
+//! # let mut kernel = Kernel { ticks: 0 };
+//! # let mut frame = TrapFrame::new();
+//! let dispatcher = SyscallDispatcher::new(&SYSCALLS, SyscallNumber::X8);
+//! if dispatcher.dispatch_current(&mut kernel, &mut frame) == SyscallResult::Unhandled {
+//!   /* terminate the task */
+//! }
+//!
+//! // start the first task
+//! # let (entry, stack_top) = (VirtAddr::new(0x40_0000), VirtAddr::new(0x80_0000));
+//! unsafe { enter_user(entry, stack_top, 0) };
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::VirtAddr;
+use crate::exception::{ec, TrapFrame};
+#[cfg(target_arch = "aarch64")]
+use crate::register::el0::sp_el0;
+use crate::register::el1::esr_el1;
+#[cfg(target_arch = "aarch64")]
+use crate::register::el1::{elr_el1, spsr_el1};
+#[cfg(target_arch = "aarch64")]
+use core::arch::asm;
+
+/// Number of general purpose registers passing arguments to a system call
+pub const MAX_ARGS: usize = 6;
+
+/// Register holding the system call number if it is not encoded in the ``SVC`` immediate
+const NUMBER_REGISTER: usize = 8;
+
+/// Enter the task at ``entry`` running at EL0 using ``SP_EL0`` with the stack ``sp``. The task is entered with all
+/// interrupts unmasked and ``arg`` passed in ``x0``. All other general purpose registers, the SIMD&FP registers,
+/// ``FPCR``, ``FPSR`` and the thread ID registers ``TPIDR_EL0`` and ``TPIDRRO_EL0`` are cleared to not leak kernel
+/// data to the task. All exceptions are masked at EL1 while the return state of the task is set up.
+///
+/// # Safety
+/// Needs to be called at EL1 with the access to the SIMD&FP registers enabled in ``CPACR_EL1::FPEN``. The entry and
+/// the stack need to be mapped accessible at EL0 and the exception vectors of EL1 need to be set up to handle the
+/// exceptions and interrupts taken from the task.
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn enter_user(entry: VirtAddr, sp: VirtAddr, arg: u64) -> ! {
+  // mask all exceptions before setting up the return state, an exception taken in between would overwrite ELR_EL1 and
+  // SPSR_EL1
+  asm!("msr daifset, #0xf", options(nomem, nostack));
+  sp_el0::set(sp.as_u64());
+  elr_el1::set(entry.as_u64());
+  spsr_el1::set(
+    (spsr_el1::M::EL0t
+      | spsr_el1::D::UNMASKED
+      | spsr_el1::A::UNMASKED
+      | spsr_el1::I::UNMASKED
+      | spsr_el1::F::UNMASKED)
+      .raw_value(),
+  );
+  asm!(
+    "movi v0.2d, #0",
+    "movi v1.2d, #0",
+    "movi v2.2d, #0",
+    "movi v3.2d, #0",
+    "movi v4.2d, #0",
+    "movi v5.2d, #0",
+    "movi v6.2d, #0",
+    "movi v7.2d, #0",
+    "movi v8.2d, #0",
+    "movi v9.2d, #0",
+    "movi v10.2d, #0",
+    "movi v11.2d, #0",
+    "movi v12.2d, #0",
+    "movi v13.2d, #0",
+    "movi v14.2d, #0",
+    "movi v15.2d, #0",
+    "movi v16.2d, #0",
+    "movi v17.2d, #0",
+    "movi v18.2d, #0",
+    "movi v19.2d, #0",
+    "movi v20.2d, #0",
+    "movi v21.2d, #0",
+    "movi v22.2d, #0",
+    "movi v23.2d, #0",
+    "movi v24.2d, #0",
+    "movi v25.2d, #0",
+    "movi v26.2d, #0",
+    "movi v27.2d, #0",
+    "movi v28.2d, #0",
+    "movi v29.2d, #0",
+    "movi v30.2d, #0",
+    "movi v31.2d, #0",
+    "msr fpcr, xzr",
+    "msr fpsr, xzr",
+    "msr tpidr_el0, xzr",
+    "msr tpidrro_el0, xzr",
+    "mov x1, xzr",
+    "mov x2, xzr",
+    "mov x3, xzr",
+    "mov x4, xzr",
+    "mov x5, xzr",
+    "mov x6, xzr",
+    "mov x7, xzr",
+    "mov x8, xzr",
+    "mov x9, xzr",
+    "mov x10, xzr",
+    "mov x11, xzr",
+    "mov x12, xzr",
+    "mov x13, xzr",
+    "mov x14, xzr",
+    "mov x15, xzr",
+    "mov x16, xzr",
+    "mov x17, xzr",
+    "mov x18, xzr",
+    "mov x19, xzr",
+    "mov x20, xzr",
+    "mov x21, xzr",
+    "mov x22, xzr",
+    "mov x23, xzr",
+    "mov x24, xzr",
+    "mov x25, xzr",
+    "mov x26, xzr",
+    "mov x27, xzr",
+    "mov x28, xzr",
+    "mov x29, xzr",
+    "mov x30, xzr",
+    "eret",
+    in("x0") arg,
+    options(noreturn)
+  )
+}
+
+/// An ``SVC`` instruction executed in AArch64 state as decoded from the syndrome
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SvcCall {
+  /// The immediate value of the ``SVC`` instruction
+  pub immediate: u16,
+}
+
+impl SvcCall {
+  /// Decode the call from the raw ``ESR_EL1`` value. ``None`` is returned if the exception is not an ``SVC``
+  /// instruction execution in AArch64 state.
+  pub fn decode(esr: u32) -> Option<Self> {
+    let class = (esr & esr_el1::EC::Field.mask()) >> esr_el1::EC::Field.shift();
+    if class != ec::SVC64 {
+      return None;
+    }
+    Some(Self {
+      immediate: (esr & 0xFFFF) as u16,
+    })
+  }
+}
+
+/// The source of the system call number
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyscallNumber {
+  /// The immediate value of the ``SVC`` instruction
+  Immediate,
+  /// The value of ``x8`` with ``SVC #0``, as used by the Linux system call convention. ``SVC`` instructions with any
+  /// other immediate are not handled.
+  X8,
+}
+
+/// The view onto the trap frame of a system call, with the arguments in ``x0`` to ``x5`` and the return value in
+/// ``x0``
+pub struct SyscallArgs<'a> {
+  number: u64,
+  frame: &'a mut TrapFrame,
+}
+
+impl<'a> SyscallArgs<'a> {
+  /// Create the view onto the trap frame for the system call with the given number
+  pub fn new(number: u64, frame: &'a mut TrapFrame) -> Self {
+    Self { number, frame }
+  }
+
+  /// The number of the system call
+  pub fn number(&self) -> u64 {
+    self.number
+  }
+
+  /// The argument with the given index
+  ///
+  /// # Panics
+  /// If the index exceeds ``MAX_ARGS``
+  pub fn arg(&self, index: usize) -> u64 {
+    assert!(index < MAX_ARGS, "system call argument index out of range");
+    self.frame.x[index]
+  }
+
+  /// All arguments of the system call
+  pub fn args(&self) -> [u64; MAX_ARGS] {
+    let mut args = [0; MAX_ARGS];
+    args.copy_from_slice(&self.frame.x[..MAX_ARGS]);
+    args
+  }
+
+  /// Write the return value of the system call to ``x0``
+  pub fn set_return(&mut self, value: u64) {
+    self.frame.x[0] = value;
+  }
+
+  /// The trap frame of the calling task
+  pub fn frame(&mut self) -> &mut TrapFrame {
+    self.frame
+  }
+}
+
+/// A system call implemented by the kernel. The handler gets access to the kernel state and the arguments of the
+/// calling task, its result is returned to the task in ``x0``.
+pub struct Syscall<C> {
+  /// The number of the system call
+  pub number: u64,
+  /// Perform the system call
+  pub handler: fn(&mut C, &mut SyscallArgs) -> u64,
+}
+
+/// The result of dispatching a system call
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyscallResult {
+  /// The system call has been performed and its result written back to the trap frame
+  Handled,
+  /// The exception is not a system call or the system call is not implemented, the trap frame is unchanged
+  Unhandled,
+}
+
+/// Dispatches ``SVC`` exceptions taken from EL0 with a table of system calls
+pub struct SyscallDispatcher<'a, C> {
+  syscalls: &'a [Syscall<C>],
+  number: SyscallNumber,
+}
+
+impl<'a, C> SyscallDispatcher<'a, C> {
+  /// Create the dispatcher for the given system calls, taking the system call number from the given source
+  pub const fn new(syscalls: &'a [Syscall<C>], number: SyscallNumber) -> Self {
+    Self { syscalls, number }
+  }
+
+  /// Look up the system call with the given number
+  pub fn lookup(&self, number: u64) -> Option<&Syscall<C>> {
+    self
+      .syscalls
+      .iter()
+      .find(|syscall| syscall.number == number)
+  }
+
+  /// Perform the system call described by the raw ``ESR_EL1`` value with the arguments from the trap frame. The
+  /// return address in the trap frame already points past the ``SVC`` instruction.
+  pub fn dispatch(&self, ctx: &mut C, frame: &mut TrapFrame, esr: u32) -> SyscallResult {
+    let call = match SvcCall::decode(esr) {
+      Some(call) => call,
+      None => return SyscallResult::Unhandled,
+    };
+    let number = match self.number {
+      SyscallNumber::Immediate => call.immediate as u64,
+      SyscallNumber::X8 if call.immediate == 0 => frame.reg(NUMBER_REGISTER),
+      SyscallNumber::X8 => return SyscallResult::Unhandled,
+    };
+    let syscall = match self.lookup(number) {
+      Some(syscall) => syscall,
+      None => return SyscallResult::Unhandled,
+    };
+    let mut args = SyscallArgs::new(number, frame);
+    let result = (syscall.handler)(ctx, &mut args);
+    args.set_return(result);
+    SyscallResult::Handled
+  }
+
+  /// Perform the system call described by the current contents of ``ESR_EL1``
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn dispatch_current(&self, ctx: &mut C, frame: &mut TrapFrame) -> SyscallResult {
+    self.dispatch(ctx, frame, esr_el1::get())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Kernel {
+    calls: usize,
+  }
+
+  static SYSCALLS: [Syscall<Kernel>; 2] = [
+    Syscall {
+      number: 0,
+      handler: |kernel, _| {
+        kernel.calls += 1;
+        42
+      },
+    },
+    Syscall {
+      number: 64,
+      handler: |kernel, args| {
+        kernel.calls += 1;
+        args.arg(0) + args.arg(5)
+      },
+    },
+  ];
+
+  /// Syndrome of an ``SVC`` instruction with the given immediate
+  const fn svc(immediate: u16) -> u32 {
+    (ec::SVC64 << 26) | (1 << 25) | immediate as u32
+  }
+
+  #[test]
+  fn dispatch_by_immediate() {
+    let dispatcher = SyscallDispatcher::new(&SYSCALLS, SyscallNumber::Immediate);
+    let mut kernel = Kernel { calls: 0 };
+    let mut frame = TrapFrame::new();
+    frame.x[0] = 1;
+    frame.x[5] = 2;
+    assert_eq!(
+      dispatcher.dispatch(&mut kernel, &mut frame, svc(64)),
+      SyscallResult::Handled
+    );
+    assert_eq!(frame.x[0], 3);
+    assert_eq!(
+      dispatcher.dispatch(&mut kernel, &mut frame, svc(1)),
+      SyscallResult::Unhandled
+    );
+    assert_eq!(kernel.calls, 1);
+  }
+
+  #[test]
+  fn dispatch_by_x8() {
+    let dispatcher = SyscallDispatcher::new(&SYSCALLS, SyscallNumber::X8);
+    let mut kernel = Kernel { calls: 0 };
+    let mut frame = TrapFrame::new();
+    frame.x[8] = 64;
+    frame.x[0] = 5;
+    assert_eq!(
+      dispatcher.dispatch(&mut kernel, &mut frame, svc(0)),
+      SyscallResult::Handled
+    );
+    assert_eq!(frame.x[0], 5);
+    // a non zero immediate does not follow the convention even if x8 names a valid system call
+    frame.x[8] = 0;
+    assert_eq!(
+      dispatcher.dispatch(&mut kernel, &mut frame, svc(1)),
+      SyscallResult::Unhandled
+    );
+    assert_eq!(frame.x[0], 5);
+    assert_eq!(kernel.calls, 1);
+  }
+
+  #[test]
+  fn other_exceptions_are_not_dispatched() {
+    let dispatcher = SyscallDispatcher::new(&SYSCALLS, SyscallNumber::Immediate);
+    let mut kernel = Kernel { calls: 0 };
+    let mut frame = TrapFrame::new();
+    // HVC #0
+    assert_eq!(
+      dispatcher.dispatch(&mut kernel, &mut frame, 0x5A00_0000),
+      SyscallResult::Unhandled
+    );
+    assert_eq!(kernel.calls, 0);
+  }
+}
@@ -0,0 +1,262 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # User Memory Access
+//!
+//! Copy data between EL1 and buffers passed by untrusted EL0 code. The accesses to the user memory use the
+//! unprivileged load and store instructions, so they are checked against the EL0 permissions of the translation and
+//! keep working while ``PSTATE.PAN`` is set. Each of these instructions is recorded in the exception fixup table. A
+//! data abort caused by one of them is redirected by ``fixup_exception`` to a recovery path, turning the fault into
+//! an ``Err(Fault)`` of the access function instead of an unhandled exception.
+//!
+//! While ``PSTATE.UAO`` is set the unprivileged instructions behave like their privileged counterparts and would
+//! bypass the EL0 permission check. ``enable_pan`` clears it and each exception taken to EL1 clears it as well, so
+//! the access functions must not be called from code that sets ``PSTATE.UAO`` itself.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::addr::VirtAddr;
+//! # use ruspiro_arch_aarch64::exception::TrapFrame;
+//! # use ruspiro_arch_aarch64::uaccess::*;
+//! // the data abort handler of EL1 for aborts taken from EL1
+//! fn data_abort_current(frame: &mut TrapFrame) {
+//!   if !fixup_exception(frame) {
+//!     panic!("kernel data abort at {:#x}", frame.elr);
+//!   }
+//! }
+//!
+//! // a system call handler reading a buffer from the calling process
+//! fn sys_write(buffer: u64, len: usize) -> Result<usize, Fault> {
+//!   let mut data = [0u8; 64];
+//!   let len = len.min(data.len());
+//!   copy_from_user(&mut data[..len], VirtAddr::new(buffer))?;
+//!   Ok(len)
+//! }
+//! ```
+//!
+//! The exception fixup table is placed in the section ``ruspiro_extable``. The linker script need to keep this section
+//! as output section of the same name, so the linker provides the symbols of its start and end:
+//! ```text
+//! ruspiro_extable : { KEEP(*(ruspiro_extable)) }
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::addr::VirtAddr;
+#[cfg(target_arch = "aarch64")]
+use crate::exception::TrapFrame;
+#[cfg(target_arch = "aarch64")]
+use crate::instructions::isb;
+#[cfg(target_arch = "aarch64")]
+use crate::register::el1::{id_aa64mmfr2_el1, sctlr_el1};
+#[cfg(target_arch = "aarch64")]
+use crate::register::{pan, uao};
+#[cfg(target_arch = "aarch64")]
+use core::arch::global_asm;
+#[cfg(target_arch = "aarch64")]
+use core::{mem, slice};
+
+/// The access to the user memory caused a data abort
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fault;
+
+/// An entry of the exception fixup table
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExceptionTableEntry {
+  /// The address of the instruction that may fault
+  pub insn: u64,
+  /// The address execution continues at if the instruction faults
+  pub fixup: u64,
+}
+
+#[cfg(target_arch = "aarch64")]
+extern "C" {
+  static __start_ruspiro_extable: ExceptionTableEntry;
+  static __stop_ruspiro_extable: ExceptionTableEntry;
+  fn __ruspiro_copy_from_user(dst: *mut u8, src: u64, len: usize) -> usize;
+  fn __ruspiro_copy_to_user(dst: u64, src: *const u8, len: usize) -> usize;
+}
+
+/// The exception fixup table as placed by the linker
+#[cfg(target_arch = "aarch64")]
+pub fn exception_table() -> &'static [ExceptionTableEntry] {
+  unsafe {
+    let start = &__start_ruspiro_extable as *const ExceptionTableEntry;
+    let stop = &__stop_ruspiro_extable as *const ExceptionTableEntry;
+    slice::from_raw_parts(start, stop.offset_from(start) as usize)
+  }
+}
+
+/// Search the given table for the fixup address of the instruction at ``elr``
+pub fn search_exception_table(table: &[ExceptionTableEntry], elr: u64) -> Option<u64> {
+  table
+    .iter()
+    .find(|entry| entry.insn == elr)
+    .map(|entry| entry.fixup)
+}
+
+/// Redirect the return address of a data abort taken from EL1 to the recovery path, if the faulting instruction is
+/// a user memory access. Returns ``false`` if the abort was not caused by a user memory access.
+#[cfg(target_arch = "aarch64")]
+pub fn fixup_exception(frame: &mut TrapFrame) -> bool {
+  match search_exception_table(exception_table(), frame.elr) {
+    Some(fixup) => {
+      frame.elr = fixup;
+      true
+    }
+    None => false,
+  }
+}
+
+/// Copy the contents of the user buffer at ``src`` into ``dst``. On a fault ``dst`` may be partially written.
+#[cfg(target_arch = "aarch64")]
+pub fn copy_from_user(dst: &mut [u8], src: VirtAddr) -> Result<(), Fault> {
+  match unsafe { __ruspiro_copy_from_user(dst.as_mut_ptr(), src.as_u64(), dst.len()) } {
+    0 => Ok(()),
+    _ => Err(Fault),
+  }
+}
+
+/// Copy the contents of ``src`` into the user buffer at ``dst``. On a fault the user buffer may be partially written.
+#[cfg(target_arch = "aarch64")]
+pub fn copy_to_user(dst: VirtAddr, src: &[u8]) -> Result<(), Fault> {
+  match unsafe { __ruspiro_copy_to_user(dst.as_u64(), src.as_ptr(), src.len()) } {
+    0 => Ok(()),
+    _ => Err(Fault),
+  }
+}
+
+mod private {
+  pub trait Sealed {}
+}
+
+/// The plain integer types that can be read from and written to user memory with ``get_user`` and ``put_user``
+pub trait UserValue: private::Sealed + Copy + Default {}
+
+macro_rules! impl_user_value {
+  ($($t:ty),*) => {
+    $(
+      impl private::Sealed for $t {}
+      impl UserValue for $t {}
+    )*
+  };
+}
+
+impl_user_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Read a single value from the user memory at ``src``
+#[cfg(target_arch = "aarch64")]
+pub fn get_user<T: UserValue>(src: VirtAddr) -> Result<T, Fault> {
+  let mut value = T::default();
+  let bytes =
+    unsafe { slice::from_raw_parts_mut(&mut value as *mut T as *mut u8, mem::size_of::<T>()) };
+  copy_from_user(bytes, src)?;
+  Ok(value)
+}
+
+/// Write a single value to the user memory at ``dst``
+#[cfg(target_arch = "aarch64")]
+pub fn put_user<T: UserValue>(dst: VirtAddr, value: T) -> Result<(), Fault> {
+  let bytes =
+    unsafe { slice::from_raw_parts(&value as *const T as *const u8, mem::size_of::<T>()) };
+  copy_to_user(dst, bytes)
+}
+
+/// Prevent EL1 from accessing memory accessible at EL0 other than through the user memory access functions. Sets
+/// ``PSTATE.PAN`` and configures ``SCTLR_EL1::SPAN`` to set it on every exception taken to EL1. ``PSTATE.UAO`` is
+/// cleared if implemented, so the user memory accesses are checked against the EL0 permissions. Requires ARMv8.1
+#[cfg(target_arch = "aarch64")]
+pub fn enable_pan() {
+  sctlr_el1::write(sctlr_el1::SPAN::SET_PAN);
+  isb();
+  pan::write(pan::PAN::ENABLE);
+  if id_aa64mmfr2_el1::read(id_aa64mmfr2_el1::UAO::Field) == id_aa64mmfr2_el1::UAO::SUPPORTED {
+    uao::write(uao::UAO::DISABLE);
+  }
+}
+
+// user memory copy routines: x0 destination, x1 source, x2 length, returning the number of bytes not copied in x0.
+// The unprivileged access to the user memory is recorded in the exception fixup table with the recovery path
+// returning the remaining length
+#[cfg(target_arch = "aarch64")]
+global_asm!(
+  ".section .text.__ruspiro_copy_from_user, \"ax\"",
+  ".global __ruspiro_copy_from_user",
+  ".type __ruspiro_copy_from_user, %function",
+  ".balign 4",
+  "__ruspiro_copy_from_user:",
+  "  cbz x2, 2f",
+  "1:",
+  "  ldtrb w3, [x1]",
+  "  strb w3, [x0], #1",
+  "  add x1, x1, #1",
+  "  subs x2, x2, #1",
+  "  b.ne 1b",
+  "2:",
+  "  mov x0, x2",
+  "  ret",
+  ".pushsection ruspiro_extable, \"a\"",
+  ".balign 8",
+  ".quad 1b, 2b",
+  ".popsection",
+  ".size __ruspiro_copy_from_user, . - __ruspiro_copy_from_user",
+  "",
+  ".section .text.__ruspiro_copy_to_user, \"ax\"",
+  ".global __ruspiro_copy_to_user",
+  ".type __ruspiro_copy_to_user, %function",
+  ".balign 4",
+  "__ruspiro_copy_to_user:",
+  "  cbz x2, 2f",
+  "3:",
+  "  ldrb w3, [x1], #1",
+  "1:",
+  "  sttrb w3, [x0]",
+  "  add x0, x0, #1",
+  "  subs x2, x2, #1",
+  "  b.ne 3b",
+  "2:",
+  "  mov x0, x2",
+  "  ret",
+  ".pushsection ruspiro_extable, \"a\"",
+  ".balign 8",
+  ".quad 1b, 2b",
+  ".popsection",
+  ".size __ruspiro_copy_to_user, . - __ruspiro_copy_to_user",
+);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const TABLE: [ExceptionTableEntry; 3] = [
+    ExceptionTableEntry {
+      insn: 0x8_1000,
+      fixup: 0x8_1010,
+    },
+    ExceptionTableEntry {
+      insn: 0x8_1040,
+      fixup: 0x8_1058,
+    },
+    ExceptionTableEntry {
+      insn: 0x8_1020,
+      fixup: 0x8_1030,
+    },
+  ];
+
+  #[test]
+  fn search_finds_fixup_of_faulting_instruction() {
+    assert_eq!(search_exception_table(&TABLE, 0x8_1000), Some(0x8_1010));
+    assert_eq!(search_exception_table(&TABLE, 0x8_1040), Some(0x8_1058));
+    assert_eq!(search_exception_table(&TABLE, 0x8_1020), Some(0x8_1030));
+  }
+
+  #[test]
+  fn search_ignores_other_instructions() {
+    assert_eq!(search_exception_table(&TABLE, 0x8_1004), None);
+    assert_eq!(search_exception_table(&TABLE, 0x8_1010), None);
+    assert_eq!(search_exception_table(&[], 0x8_1000), None);
+  }
+}
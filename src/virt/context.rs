@@ -0,0 +1,162 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # EL1 System Register Context
+//!
+//! The EL1 and EL0 system register state of a virtual CPU. When switching between guests the hypervisor saves the
+//! state of the outgoing virtual CPU and restores the one of the incoming virtual CPU before returning to the guest.
+//! The state is accessed with the EL1 register names, so save and restore need to be called at EL2 with
+//! ``HCR_EL2::E2H`` not set. With ``E2H`` set those names would access the EL2 registers.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::virt::context::El1SysRegContext;
+//! # fn schedule(_: &El1SysRegContext) -> &El1SysRegContext { unimplemented!() }
+//! let outgoing = El1SysRegContext::save();
+//! let incoming = schedule(&outgoing);
+//! incoming.restore();
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+use crate::instructions::isb;
+#[cfg(target_arch = "aarch64")]
+use crate::register::{el0::*, el1::*, el2::cntvoff_el2};
+
+/// The EL1 and EL0 system register state of a virtual CPU
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct El1SysRegContext {
+  pub sctlr_el1: u64,
+  pub tcr_el1: u64,
+  pub ttbr0_el1: u64,
+  pub ttbr1_el1: u64,
+  pub mair_el1: u64,
+  pub amair_el1: u64,
+  pub vbar_el1: u64,
+  pub contextidr_el1: u64,
+  pub tpidr_el0: u64,
+  pub tpidrro_el0: u64,
+  pub tpidr_el1: u64,
+  pub sp_el0: u64,
+  pub sp_el1: u64,
+  pub elr_el1: u64,
+  pub spsr_el1: u64,
+  pub esr_el1: u64,
+  pub far_el1: u64,
+  pub afsr0_el1: u64,
+  pub afsr1_el1: u64,
+  pub par_el1: u64,
+  pub cpacr_el1: u64,
+  pub cntkctl_el1: u64,
+  /// Virtual timer control
+  pub cntv_ctl_el0: u64,
+  /// Virtual timer compare value
+  pub cntv_cval_el0: u64,
+  /// Offset of the virtual counter of this virtual CPU
+  pub cntvoff_el2: u64,
+}
+
+impl El1SysRegContext {
+  /// Create a context with all registers cleared
+  pub const fn new() -> Self {
+    Self {
+      sctlr_el1: 0,
+      tcr_el1: 0,
+      ttbr0_el1: 0,
+      ttbr1_el1: 0,
+      mair_el1: 0,
+      amair_el1: 0,
+      vbar_el1: 0,
+      contextidr_el1: 0,
+      tpidr_el0: 0,
+      tpidrro_el0: 0,
+      tpidr_el1: 0,
+      sp_el0: 0,
+      sp_el1: 0,
+      elr_el1: 0,
+      spsr_el1: 0,
+      esr_el1: 0,
+      far_el1: 0,
+      afsr0_el1: 0,
+      afsr1_el1: 0,
+      par_el1: 0,
+      cpacr_el1: 0,
+      cntkctl_el1: 0,
+      cntv_ctl_el0: 0,
+      cntv_cval_el0: 0,
+      cntvoff_el2: 0,
+    }
+  }
+
+  /// Save the EL1 and EL0 system register state of the current virtual CPU. The virtual timer is disabled afterwards
+  /// to prevent it from firing while another virtual CPU is running
+  #[cfg(target_arch = "aarch64")]
+  pub fn save() -> Self {
+    let ctx = Self {
+      sctlr_el1: sctlr_el1::get(),
+      tcr_el1: tcr_el1::get(),
+      ttbr0_el1: ttbr0_el1::get(),
+      ttbr1_el1: ttbr1_el1::get(),
+      mair_el1: mair_el1::get(),
+      amair_el1: amair_el1::get(),
+      vbar_el1: vbar_el1::get(),
+      contextidr_el1: contextidr_el1::get(),
+      tpidr_el0: tpidr_el0::get(),
+      tpidrro_el0: tpidrro_el0::get(),
+      tpidr_el1: tpidr_el1::get(),
+      sp_el0: sp_el0::get(),
+      sp_el1: sp_el1::get(),
+      elr_el1: elr_el1::get(),
+      spsr_el1: spsr_el1::get(),
+      esr_el1: esr_el1::get() as u64,
+      far_el1: far_el1::get(),
+      afsr0_el1: afsr0_el1::get(),
+      afsr1_el1: afsr1_el1::get(),
+      par_el1: par_el1::get(),
+      cpacr_el1: cpacr_el1::get(),
+      cntkctl_el1: cntkctl_el1::get(),
+      cntv_ctl_el0: cntv_ctl_el0::get(),
+      cntv_cval_el0: cntv_cval_el0::get(),
+      cntvoff_el2: cntvoff_el2::get(),
+    };
+    cntv_ctl_el0::write(cntv_ctl_el0::ENABLE::DISABLE);
+    ctx
+  }
+
+  /// Restore the EL1 and EL0 system register state of a virtual CPU. A single context synchronization at the end
+  /// makes the whole state visible at once, so there is no point in time where a partially restored EL1&0 translation
+  /// regime could be used. The virtual timer is restored after its offset and compare value to not raise a spurious
+  /// timer interrupt.
+  #[cfg(target_arch = "aarch64")]
+  pub fn restore(&self) {
+    sctlr_el1::set(self.sctlr_el1);
+    tcr_el1::set(self.tcr_el1);
+    ttbr0_el1::set(self.ttbr0_el1);
+    ttbr1_el1::set(self.ttbr1_el1);
+    mair_el1::set(self.mair_el1);
+    amair_el1::set(self.amair_el1);
+    vbar_el1::set(self.vbar_el1);
+    contextidr_el1::set(self.contextidr_el1);
+    tpidr_el0::set(self.tpidr_el0);
+    tpidrro_el0::set(self.tpidrro_el0);
+    tpidr_el1::set(self.tpidr_el1);
+    sp_el0::set(self.sp_el0);
+    sp_el1::set(self.sp_el1);
+    elr_el1::set(self.elr_el1);
+    spsr_el1::set(self.spsr_el1);
+    esr_el1::set(self.esr_el1 as u32);
+    far_el1::set(self.far_el1);
+    afsr0_el1::set(self.afsr0_el1);
+    afsr1_el1::set(self.afsr1_el1);
+    par_el1::set(self.par_el1);
+    cpacr_el1::set(self.cpacr_el1);
+    cntkctl_el1::set(self.cntkctl_el1);
+    cntvoff_el2::set(self.cntvoff_el2);
+    cntv_cval_el0::set(self.cntv_cval_el0);
+    cntv_ctl_el0::set(self.cntv_ctl_el0);
+    isb();
+  }
+}
@@ -0,0 +1,216 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Virtual Exception Injection
+//!
+//! Functions to inject exceptions into a guest running at EL1. A synchronous exception is injected by doing what the
+//! hardware would do when taking the exception to EL1: the syndrome is written to the guest ``ESR_EL1``/``FAR_EL1``,
+//! the interrupted guest state is preserved in the guest ``ELR_EL1``/``SPSR_EL1`` and the guest resumes at the
+//! matching entry of its vector table. The guest state is taken from the saved ``El1SysRegContext`` of the virtual CPU
+//! and from the ``TrapFrame`` whose ``elr`` and ``spsr`` are restored to ``ELR_EL2``/``SPSR_EL2`` when returning to
+//! the guest.
+//!
+//! Asynchronous exceptions are injected by marking a virtual IRQ, FIQ or SError pending in ``HCR_EL2``. They are taken
+//! by the guest as soon as it unmasks them.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::virt::{context::El1SysRegContext, inject::*};
+//! # use ruspiro_arch_aarch64::exception::TrapFrame;
+//! # let mut ctx = El1SysRegContext::new();
+//! # let mut frame = TrapFrame::new();
+//! // the guest executed an instruction the hypervisor does not emulate
+//! inject_undefined(&mut ctx, &mut frame);
+//!
+//! // signal a pending interrupt of the virtual interrupt controller
+//! set_virtual_irq(true);
+//! ```
+
+use super::context::El1SysRegContext;
+use crate::exception::{ec, TrapFrame};
+#[cfg(target_arch = "aarch64")]
+use crate::register::el2::hcr_el2;
+
+/// Execution state bit of the saved PSTATE, set for AArch32
+const SPSR_NRW: u64 = 1 << 4;
+/// Exception level and stack pointer selection of the saved PSTATE
+const SPSR_M_MASK: u64 = 0b1111;
+/// EL1 using SP_EL1
+const SPSR_M_EL1H: u64 = 0b0101;
+/// EL1 using SP_EL0
+const SPSR_M_EL1T: u64 = 0b0100;
+/// Debug, SError, IRQ and FIQ mask bits of the saved PSTATE
+const SPSR_DAIF: u64 = 0b1111 << 6;
+/// Condition flags of the saved PSTATE
+const SPSR_NZCV: u64 = 0b1111 << 28;
+/// Instruction length bit of the syndrome, injected exceptions always report a 32Bit instruction
+const ESR_IL: u64 = 1 << 25;
+/// Position of the exception class within the syndrome
+const ESR_EC_SHIFT: u64 = 26;
+/// Instruction specific syndrome bits
+const ESR_ISS_MASK: u32 = 0x01FF_FFFF;
+
+/// The kind of an exception selecting the entry within a group of the vector table
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u64)]
+pub enum ExceptionKind {
+  /// Synchronous exception
+  Synchronous = 0x000,
+  /// IRQ or virtual IRQ
+  Irq = 0x080,
+  /// FIQ or virtual FIQ
+  Fiq = 0x100,
+  /// SError or virtual SError
+  SError = 0x180,
+}
+
+/// The offset from ``VBAR_EL1`` of the vector an exception of the given kind is taken to, if the guest was
+/// interrupted with the given saved PSTATE
+pub const fn vector_offset(spsr: u64, kind: ExceptionKind) -> u64 {
+  let group = if spsr & SPSR_NRW != 0 {
+    // lower exception level using AArch32
+    0x600
+  } else {
+    match spsr & SPSR_M_MASK {
+      SPSR_M_EL1T => 0x000,
+      SPSR_M_EL1H => 0x200,
+      // lower exception level using AArch64
+      _ => 0x400,
+    }
+  };
+  group + kind as u64
+}
+
+/// The PSTATE the guest resumes with when taking an exception to EL1. The condition flags are preserved, all
+/// exceptions get masked and the guest runs at EL1 using SP_EL1
+pub const fn injected_spsr(spsr: u64) -> u64 {
+  (spsr & SPSR_NZCV) | SPSR_DAIF | SPSR_M_EL1H
+}
+
+/// Check whether the guest was interrupted at EL0
+pub const fn from_el0(spsr: u64) -> bool {
+  spsr & SPSR_NRW != 0 || spsr & SPSR_M_MASK == 0
+}
+
+/// Inject a synchronous exception with the given syndrome and fault address into the guest
+pub fn inject_sync(ctx: &mut El1SysRegContext, frame: &mut TrapFrame, esr: u64, far: Option<u64>) {
+  ctx.esr_el1 = esr;
+  if let Some(far) = far {
+    ctx.far_el1 = far;
+  }
+  ctx.elr_el1 = frame.elr;
+  ctx.spsr_el1 = frame.spsr;
+  frame.elr = ctx.vbar_el1 + vector_offset(frame.spsr, ExceptionKind::Synchronous);
+  frame.spsr = injected_spsr(frame.spsr);
+}
+
+/// Inject an undefined instruction exception for the instruction the guest was interrupted at
+pub fn inject_undefined(ctx: &mut El1SysRegContext, frame: &mut TrapFrame) {
+  inject_sync(
+    ctx,
+    frame,
+    (ec::UNKNOWN as u64) << ESR_EC_SHIFT | ESR_IL,
+    None,
+  );
+}
+
+/// Inject a data abort at the given virtual address. The ``iss`` provides the data abort specific syndrome like the
+/// fault status code and the access direction, any bits above the 25Bit syndrome are ignored
+pub fn inject_data_abort(ctx: &mut El1SysRegContext, frame: &mut TrapFrame, far: u64, iss: u32) {
+  let class = if from_el0(frame.spsr) {
+    ec::DABT_LOWER
+  } else {
+    ec::DABT_CURRENT
+  };
+  let esr = (class as u64) << ESR_EC_SHIFT | ESR_IL | (iss & ESR_ISS_MASK) as u64;
+  inject_sync(ctx, frame, esr, Some(far));
+}
+
+/// Inject an instruction abort at the given virtual address. The ``iss`` provides the instruction abort specific
+/// syndrome like the fault status code, any bits above the 25Bit syndrome are ignored
+pub fn inject_instruction_abort(
+  ctx: &mut El1SysRegContext,
+  frame: &mut TrapFrame,
+  far: u64,
+  iss: u32,
+) {
+  let class = if from_el0(frame.spsr) {
+    ec::IABT_LOWER
+  } else {
+    ec::IABT_CURRENT
+  };
+  let esr = (class as u64) << ESR_EC_SHIFT | ESR_IL | (iss & ESR_ISS_MASK) as u64;
+  inject_sync(ctx, frame, esr, Some(far));
+}
+
+/// Mark a virtual IRQ pending for the guest or clear it. This requires ``HCR_EL2::IMO`` to be set
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn set_virtual_irq(pending: bool) {
+  hcr_el2::write(hcr_el2::VI::with_value(pending as u64));
+}
+
+/// Mark a virtual FIQ pending for the guest or clear it. This requires ``HCR_EL2::FMO`` to be set
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn set_virtual_fiq(pending: bool) {
+  hcr_el2::write(hcr_el2::VF::with_value(pending as u64));
+}
+
+/// Mark a virtual SError pending for the guest or clear it. This requires ``HCR_EL2::AMO`` to be set. The virtual
+/// SError is cleared by the hardware once it is taken by the guest
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub fn set_virtual_serror(pending: bool) {
+  hcr_el2::write(hcr_el2::VSE::with_value(pending as u64));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn vector_offset_of_each_group() {
+    // EL1 using SP_EL0
+    assert_eq!(vector_offset(0x3C4, ExceptionKind::Synchronous), 0x000);
+    assert_eq!(vector_offset(0x3C4, ExceptionKind::SError), 0x180);
+    // EL1 using SP_EL1
+    assert_eq!(vector_offset(0x3C5, ExceptionKind::Synchronous), 0x200);
+    assert_eq!(vector_offset(0x3C5, ExceptionKind::Irq), 0x280);
+    // EL0 using AArch64
+    assert_eq!(vector_offset(0x000, ExceptionKind::Fiq), 0x500);
+    // EL0 using AArch32 in user mode
+    assert_eq!(vector_offset(0x010, ExceptionKind::Synchronous), 0x600);
+    assert_eq!(vector_offset(0x010, ExceptionKind::SError), 0x780);
+  }
+
+  #[test]
+  fn data_abort_from_el0() {
+    let mut ctx = El1SysRegContext::new();
+    ctx.vbar_el1 = 0x8_0000;
+    let mut frame = TrapFrame::new();
+    frame.elr = 0x40_1000;
+    frame.spsr = 0x2000_0000;
+    // the bits above the syndrome must not change the exception class
+    inject_data_abort(&mut ctx, &mut frame, 0xDEAD_0000, 0xFE00_0047);
+    assert_eq!(ctx.esr_el1, 0x9200_0047);
+    assert_eq!(ctx.far_el1, 0xDEAD_0000);
+    assert_eq!(ctx.elr_el1, 0x40_1000);
+    assert_eq!(ctx.spsr_el1, 0x2000_0000);
+    assert_eq!(frame.elr, 0x8_0400);
+    assert_eq!(frame.spsr, 0x2000_03C5);
+  }
+
+  #[test]
+  fn instruction_abort_at_el1() {
+    let mut ctx = El1SysRegContext::new();
+    let mut frame = TrapFrame::new();
+    frame.spsr = 0x3C5;
+    inject_instruction_abort(&mut ctx, &mut frame, 0x1000, 0xFFFF_FFFF);
+    assert_eq!(ctx.esr_el1, 0x87FF_FFFF);
+    assert_eq!(frame.elr, 0x200);
+  }
+}
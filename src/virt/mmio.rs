@@ -0,0 +1,252 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # MMIO Access Emulation
+//!
+//! A guest accessing an intermediate physical address (IPA) without a valid stage 2 mapping causes a data abort that
+//! is taken to EL2. If the syndrome is valid (``ISV``) it fully describes the load or store, so the hypervisor can
+//! emulate the device access and return to the instruction following the faulting one.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::virt::mmio::*;
+//! # use ruspiro_arch_aarch64::addr::IntermediatePhysAddr;
+//! # use ruspiro_arch_aarch64::exception::TrapFrame;
+//! // ldr w1, [x0] with x0 = 0x3F20_0004 hitting a stage 2 translation fault at level 3
+//! let access = MmioAccess::decode(0x9381_0007, 0x003F_2000, 0x3F20_0004).unwrap();
+//! assert_eq!(access.ipa, IntermediatePhysAddr::new(0x3F20_0004));
+//! assert_eq!(access.width, 4);
+//! assert_eq!(access.register, 1);
+//! assert!(!access.write);
+//!
+//! struct Gpio;
+//! impl MmioHandler for Gpio {
+//!   fn read(&mut self, _ipa: IntermediatePhysAddr, _width: usize) -> u64 { 0xFFFF_FFFF }
+//!   fn write(&mut self, _ipa: IntermediatePhysAddr, _width: usize, _value: u64) {}
+//! }
+//!
+//! let mut frame = TrapFrame::new();
+//! access.emulate(&mut frame, &mut Gpio);
+//! assert_eq!(frame.x[1], 0xFFFF_FFFF);
+//! assert_eq!(frame.elr, 4);
+//! ```
+
+use crate::addr::IntermediatePhysAddr;
+use crate::exception::{ec, TrapFrame};
+#[cfg(target_arch = "aarch64")]
+use crate::register::el2::far_el2;
+use crate::register::el2::{esr_el2, hpfar_el2};
+
+/// Instruction syndrome valid
+const ISV: u32 = 1 << 24;
+/// Syndrome access size
+const SAS_SHIFT: u32 = 22;
+/// Syndrome sign extend
+const SSE: u32 = 1 << 21;
+/// Syndrome register transfer
+const SRT_SHIFT: u32 = 16;
+/// Sixty four bit general purpose register transfer
+const SF: u32 = 1 << 15;
+/// Write not read
+const WNR: u32 = 1 << 6;
+
+/// A device access that can be performed on behalf of a guest
+pub trait MmioHandler {
+  /// Read ``width`` bytes from the device register at the given IPA
+  fn read(&mut self, ipa: IntermediatePhysAddr, width: usize) -> u64;
+  /// Write the lower ``width`` bytes of the value to the device register at the given IPA
+  fn write(&mut self, ipa: IntermediatePhysAddr, width: usize, value: u64);
+}
+
+/// A guest load or store to an emulated device as decoded from a data abort syndrome
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MmioAccess {
+  /// The intermediate physical address accessed
+  pub ipa: IntermediatePhysAddr,
+  /// The access width in bytes
+  pub width: usize,
+  /// The loaded value is sign extended to the register width
+  pub sign_extend: bool,
+  /// The number of the general purpose register transferred, 31 is the zero register
+  pub register: usize,
+  /// The access is a store
+  pub write: bool,
+  /// The register transferred is a 64Bit ``Xn`` register, otherwise a 32Bit ``Wn`` register
+  pub sixty_four: bool,
+  /// The length of the faulting instruction in bytes
+  pub instruction_length: u64,
+}
+
+impl MmioAccess {
+  /// Decode the access from the raw ``ESR_EL2``, ``HPFAR_EL2`` and ``FAR_EL2`` values. ``None`` is returned if the
+  /// exception is not a data abort from a lower exception level or if the syndrome does not describe the access, for
+  /// example for load/store pair or writeback instructions.
+  pub fn decode(esr: u32, hpfar: u64, far: u64) -> Option<Self> {
+    let class = (esr & esr_el2::EC::Field.mask()) >> esr_el2::EC::Field.shift();
+    if class != ec::DABT_LOWER || esr & ISV == 0 {
+      return None;
+    }
+    let fipa = (hpfar & hpfar_el2::FIPA::Field.mask()) >> hpfar_el2::FIPA::Field.shift();
+    Some(Self {
+      ipa: IntermediatePhysAddr::new((fipa << 12) | (far & 0xFFF)),
+      width: 1 << ((esr >> SAS_SHIFT) & 0b11),
+      sign_extend: esr & SSE != 0,
+      register: ((esr >> SRT_SHIFT) & 0b1_1111) as usize,
+      write: esr & WNR != 0,
+      sixty_four: esr & SF != 0,
+      instruction_length: if esr & esr_el2::IL::Field.mask() != 0 {
+        4
+      } else {
+        2
+      },
+    })
+  }
+
+  /// Decode the access from the current contents of ``ESR_EL2``, ``HPFAR_EL2`` and ``FAR_EL2``
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn current() -> Option<Self> {
+    Self::decode(esr_el2::get(), hpfar_el2::get(), far_el2::get())
+  }
+
+  /// The value to be stored by a write access, truncated to the access width
+  pub fn write_value(&self, frame: &TrapFrame) -> u64 {
+    frame.reg(self.register) & self.width_mask()
+  }
+
+  /// Complete a read access by storing the value read from the device into the target register. The value is
+  /// truncated to the access width, sign extended if requested and limited to 32Bit for ``Wn`` registers.
+  pub fn complete_read(&self, frame: &mut TrapFrame, value: u64) {
+    let mut value = value & self.width_mask();
+    if self.sign_extend && self.width < 8 {
+      let shift = 64 - self.width as u32 * 8;
+      value = (((value << shift) as i64) >> shift) as u64;
+    }
+    if !self.sixty_four {
+      value &= 0xFFFF_FFFF;
+    }
+    frame.set_reg(self.register, value);
+  }
+
+  /// Perform the access with the given handler and step the guest over the faulting instruction
+  pub fn emulate<H: MmioHandler>(&self, frame: &mut TrapFrame, handler: &mut H) {
+    if self.write {
+      handler.write(self.ipa, self.width, self.write_value(frame));
+    } else {
+      let value = handler.read(self.ipa, self.width);
+      self.complete_read(frame, value);
+    }
+    frame.elr += self.instruction_length;
+  }
+
+  fn width_mask(&self) -> u64 {
+    if self.width >= 8 {
+      u64::MAX
+    } else {
+      (1 << (self.width * 8)) - 1
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// A device recording the last write and answering reads with a fixed value
+  struct Device {
+    value: u64,
+    written: Option<(IntermediatePhysAddr, usize, u64)>,
+  }
+
+  impl MmioHandler for Device {
+    fn read(&mut self, _ipa: IntermediatePhysAddr, _width: usize) -> u64 {
+      self.value
+    }
+
+    fn write(&mut self, ipa: IntermediatePhysAddr, width: usize, value: u64) {
+      self.written = Some((ipa, width, value));
+    }
+  }
+
+  #[test]
+  fn decode_word_load() {
+    // ldr w1, [x0] with x0 = 0x3F20_0004
+    let access = MmioAccess::decode(0x9381_0007, 0x003F_2000, 0x3F20_0004).unwrap();
+    assert_eq!(access.ipa, IntermediatePhysAddr::new(0x3F20_0004));
+    assert_eq!(access.width, 4);
+    assert_eq!(access.register, 1);
+    assert!(!access.write);
+    assert!(!access.sign_extend);
+    assert!(!access.sixty_four);
+    assert_eq!(access.instruction_length, 4);
+  }
+
+  #[test]
+  fn decode_rejects_invalid_syndromes() {
+    // ldp w1, w2, [x0] does not provide a valid instruction syndrome
+    assert_eq!(
+      MmioAccess::decode(0x9200_0007, 0x003F_2000, 0x3F20_0004),
+      None
+    );
+    // an instruction abort from a lower exception level is no data access
+    assert_eq!(
+      MmioAccess::decode(0x8200_0007, 0x003F_2000, 0x3F20_0004),
+      None
+    );
+  }
+
+  #[test]
+  fn emulate_byte_store() {
+    // strb w2, [x0] with x0 = 0x3F20_0010
+    let access = MmioAccess::decode(0x9302_0047, 0x003F_2000, 0x3F20_0010).unwrap();
+    let mut frame = TrapFrame::new();
+    frame.x[2] = 0x1234_56AB;
+    frame.elr = 0x8_0000;
+    let mut device = Device {
+      value: 0,
+      written: None,
+    };
+    access.emulate(&mut frame, &mut device);
+    assert_eq!(
+      device.written,
+      Some((IntermediatePhysAddr::new(0x3F20_0010), 1, 0xAB))
+    );
+    assert_eq!(frame.elr, 0x8_0004);
+  }
+
+  #[test]
+  fn emulate_sign_extending_loads() {
+    let mut device = Device {
+      value: 0x8001,
+      written: None,
+    };
+    // ldrsh x3, [x0]
+    let access = MmioAccess::decode(0x9363_8007, 0x003F_2000, 0x3F20_0008).unwrap();
+    let mut frame = TrapFrame::new();
+    access.emulate(&mut frame, &mut device);
+    assert_eq!(frame.x[3], 0xFFFF_FFFF_FFFF_8001);
+    // ldrsh w3, [x0]
+    let access = MmioAccess::decode(0x9363_0007, 0x003F_2000, 0x3F20_0008).unwrap();
+    let mut frame = TrapFrame::new();
+    access.emulate(&mut frame, &mut device);
+    assert_eq!(frame.x[3], 0xFFFF_8001);
+  }
+
+  #[test]
+  fn emulate_16bit_instruction() {
+    // a T32 ldr with a 16Bit encoding clears the IL bit
+    let access = MmioAccess::decode(0x9181_0007, 0x003F_2000, 0x3F20_0004).unwrap();
+    assert_eq!(access.instruction_length, 2);
+    let mut frame = TrapFrame::new();
+    let mut device = Device {
+      value: 0x1_2345_6789,
+      written: None,
+    };
+    access.emulate(&mut frame, &mut device);
+    assert_eq!(frame.x[1], 0x2345_6789);
+    assert_eq!(frame.elr, 2);
+  }
+}
@@ -0,0 +1,16 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Virtualization Support
+//!
+//! Building blocks for a hypervisor running at EL2 that hosts guests at EL1 and EL0.
+//!
+
+pub mod context;
+pub mod inject;
+pub mod mmio;
+pub mod sysreg;
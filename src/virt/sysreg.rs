@@ -0,0 +1,287 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Trapped System Register Access Emulation
+//!
+//! Guest accesses to system registers can be trapped to EL2 with the trap controls in ``HCR_EL2`` like ``TID3``,
+//! ``TACR``, ``TVM`` or ``TSW``. The ``SysRegTrapHandler`` decodes the syndrome of such a trap (exception class 0x18),
+//! looks up the register in a table of emulated registers and performs the access on the guest trap frame. Accesses to
+//! registers that are not emulated are reported as ``Unhandled``, so the hypervisor can inject an undefined
+//! instruction exception into the guest.
+//!
+//! ```no_run
+//! # use ruspiro_arch_aarch64::virt::sysreg::*;
+//! # use ruspiro_arch_aarch64::register::sysreg::SysRegEncoding;
+//! # use ruspiro_arch_aarch64::exception::TrapFrame;
+//! struct VCpu {
+//!   actlr: u64,
+//! }
+//!
+//! static REGISTERS: [EmulatedSysReg<VCpu>; 1] = [
+//!   // ACTLR_EL1
+//!   EmulatedSysReg {
+//!     encoding: SysRegEncoding::new(3, 0, 1, 0, 1),
+//!     read: Some(|vcpu| vcpu.actlr),
+//!     write: Some(|vcpu, value| vcpu.actlr = value),
+//!   },
+//! ];
+//!
+//! # let mut vcpu = VCpu { actlr: 0 };
+//! # let mut frame = TrapFrame::new();
+//! let handler = SysRegTrapHandler::new(&REGISTERS);
+//! if handler.handle_current(&mut vcpu, &mut frame) == SysRegTrapResult::Unhandled {
+//!   /* inject an undefined instruction exception */
+//! }
+//! ```
+
+use crate::exception::{ec, TrapFrame};
+use crate::register::el2::esr_el2;
+use crate::register::sysreg::SysRegEncoding;
+
+/// Direction of the access, set for reads (``MRS``) and cleared for writes (``MSR``)
+const DIRECTION_READ: u32 = 1 << 0;
+/// Syndrome general purpose register transfered
+const RT_SHIFT: u32 = 5;
+
+/// A trapped ``MSR`` or ``MRS`` access as decoded from the syndrome
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SysRegAccess {
+  /// The system register accessed
+  pub encoding: SysRegEncoding,
+  /// The number of the general purpose register transferred, 31 is the zero register
+  pub register: usize,
+  /// The access is a read (``MRS``), otherwise a write (``MSR``)
+  pub read: bool,
+  /// The length of the trapped instruction in bytes
+  pub instruction_length: u64,
+}
+
+impl SysRegAccess {
+  /// Decode the access from the raw ``ESR_EL2`` value. ``None`` is returned if the exception is not a trapped
+  /// ``MSR``, ``MRS`` or system instruction.
+  pub fn decode(esr: u32) -> Option<Self> {
+    let class = (esr & esr_el2::EC::Field.mask()) >> esr_el2::EC::Field.shift();
+    if class != ec::SYSREG {
+      return None;
+    }
+    let iss = esr & esr_el2::ISS::Field.mask();
+    Some(Self {
+      encoding: SysRegEncoding::from_iss(iss),
+      register: ((iss >> RT_SHIFT) & 0b1_1111) as usize,
+      read: iss & DIRECTION_READ != 0,
+      instruction_length: if esr & esr_el2::IL::Field.mask() != 0 {
+        4
+      } else {
+        2
+      },
+    })
+  }
+}
+
+/// A system register emulated by the hypervisor. The callbacks get access to the state of the virtual CPU the access
+/// was trapped from. A missing callback causes the access in this direction to be unhandled.
+pub struct EmulatedSysReg<C> {
+  /// The encoding of the emulated register
+  pub encoding: SysRegEncoding,
+  /// Provide the value returned to the guest by an ``MRS``
+  pub read: Option<fn(&mut C) -> u64>,
+  /// Consume the value written by the guest with an ``MSR``
+  pub write: Option<fn(&mut C, u64)>,
+}
+
+/// The result of handling a trapped system register access
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SysRegTrapResult {
+  /// The access has been emulated and the guest continues after the trapped instruction
+  Handled,
+  /// The access could not be emulated, the guest state is unchanged
+  Unhandled,
+}
+
+/// Emulates trapped system register accesses with a table of emulated registers
+pub struct SysRegTrapHandler<'a, C> {
+  registers: &'a [EmulatedSysReg<C>],
+}
+
+impl<'a, C> SysRegTrapHandler<'a, C> {
+  /// Create the handler for the given emulated registers
+  pub const fn new(registers: &'a [EmulatedSysReg<C>]) -> Self {
+    Self { registers }
+  }
+
+  /// Look up the emulated register with the given encoding
+  pub fn lookup(&self, encoding: SysRegEncoding) -> Option<&EmulatedSysReg<C>> {
+    self.registers.iter().find(|reg| reg.encoding == encoding)
+  }
+
+  /// Emulate the trapped access described by the raw ``ESR_EL2`` value on the guest trap frame. The return address in
+  /// the trap frame is advanced past the trapped instruction if the access was handled.
+  pub fn handle(&self, vcpu: &mut C, frame: &mut TrapFrame, esr: u32) -> SysRegTrapResult {
+    let access = match SysRegAccess::decode(esr) {
+      Some(access) => access,
+      None => return SysRegTrapResult::Unhandled,
+    };
+    let reg = match self.lookup(access.encoding) {
+      Some(reg) => reg,
+      None => return SysRegTrapResult::Unhandled,
+    };
+    if access.read {
+      match reg.read {
+        Some(read) => frame.set_reg(access.register, read(vcpu)),
+        None => return SysRegTrapResult::Unhandled,
+      }
+    } else {
+      match reg.write {
+        Some(write) => write(vcpu, frame.reg(access.register)),
+        None => return SysRegTrapResult::Unhandled,
+      }
+    }
+    frame.elr += access.instruction_length;
+    SysRegTrapResult::Handled
+  }
+
+  /// Emulate the trapped access described by the current contents of ``ESR_EL2``
+  #[cfg(target_arch = "aarch64")]
+  #[inline]
+  pub fn handle_current(&self, vcpu: &mut C, frame: &mut TrapFrame) -> SysRegTrapResult {
+    self.handle(vcpu, frame, esr_el2::get())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct VCpu {
+    actlr: u64,
+    writes: usize,
+  }
+
+  /// ACTLR_EL1
+  const ACTLR: SysRegEncoding = SysRegEncoding::new(3, 0, 1, 0, 1);
+  /// ID_AA64MMFR0_EL1
+  const MMFR0: SysRegEncoding = SysRegEncoding::new(3, 0, 0, 7, 0);
+
+  static REGISTERS: [EmulatedSysReg<VCpu>; 2] = [
+    EmulatedSysReg {
+      encoding: ACTLR,
+      read: Some(|vcpu| vcpu.actlr),
+      write: Some(|vcpu, value| {
+        vcpu.actlr = value;
+        vcpu.writes += 1;
+      }),
+    },
+    EmulatedSysReg {
+      encoding: MMFR0,
+      read: Some(|_| 0x1122),
+      write: None,
+    },
+  ];
+
+  /// Syndrome of a trapped 32Bit ``MRS`` (``read``) or ``MSR`` instruction
+  const fn sysreg(encoding: SysRegEncoding, register: u32, read: bool) -> u32 {
+    (ec::SYSREG << 26) | (1 << 25) | encoding.to_iss() | (register << RT_SHIFT) | read as u32
+  }
+
+  #[test]
+  fn decode_mrs_and_msr() {
+    assert_eq!(
+      SysRegAccess::decode(sysreg(ACTLR, 3, true)),
+      Some(SysRegAccess {
+        encoding: ACTLR,
+        register: 3,
+        read: true,
+        instruction_length: 4,
+      })
+    );
+    assert_eq!(
+      SysRegAccess::decode(sysreg(MMFR0, 30, false)),
+      Some(SysRegAccess {
+        encoding: MMFR0,
+        register: 30,
+        read: false,
+        instruction_length: 4,
+      })
+    );
+    // SVC #0
+    assert_eq!(SysRegAccess::decode(0x5600_0000), None);
+  }
+
+  #[test]
+  fn read_and_write_advance_the_return_address() {
+    let handler = SysRegTrapHandler::new(&REGISTERS);
+    let mut vcpu = VCpu {
+      actlr: 0,
+      writes: 0,
+    };
+    let mut frame = TrapFrame::new();
+    frame.elr = 0x8_0000;
+    frame.x[2] = 0xABCD;
+    assert_eq!(
+      handler.handle(&mut vcpu, &mut frame, sysreg(ACTLR, 2, false)),
+      SysRegTrapResult::Handled
+    );
+    assert_eq!(vcpu.actlr, 0xABCD);
+    assert_eq!(frame.elr, 0x8_0004);
+    assert_eq!(
+      handler.handle(&mut vcpu, &mut frame, sysreg(MMFR0, 5, true)),
+      SysRegTrapResult::Handled
+    );
+    assert_eq!(frame.x[5], 0x1122);
+    assert_eq!(frame.elr, 0x8_0008);
+  }
+
+  #[test]
+  fn zero_register() {
+    let handler = SysRegTrapHandler::new(&REGISTERS);
+    let mut vcpu = VCpu {
+      actlr: 0xFFFF,
+      writes: 0,
+    };
+    let mut frame = TrapFrame::new();
+    // MSR ACTLR_EL1, XZR writes 0
+    assert_eq!(
+      handler.handle(&mut vcpu, &mut frame, sysreg(ACTLR, 31, false)),
+      SysRegTrapResult::Handled
+    );
+    assert_eq!((vcpu.actlr, vcpu.writes), (0, 1));
+    // MRS XZR, ACTLR_EL1 discards the value
+    vcpu.actlr = 0x1234;
+    assert_eq!(
+      handler.handle(&mut vcpu, &mut frame, sysreg(ACTLR, 31, true)),
+      SysRegTrapResult::Handled
+    );
+    assert!(frame.x.iter().all(|&x| x == 0));
+    assert_eq!(frame.elr, 8);
+  }
+
+  #[test]
+  fn missing_callback_or_register_is_unhandled() {
+    let handler = SysRegTrapHandler::new(&REGISTERS);
+    let mut vcpu = VCpu {
+      actlr: 0,
+      writes: 0,
+    };
+    let mut frame = TrapFrame::new();
+    frame.x[1] = 7;
+    assert_eq!(
+      handler.handle(&mut vcpu, &mut frame, sysreg(MMFR0, 1, false)),
+      SysRegTrapResult::Unhandled
+    );
+    // SCTLR_EL1
+    assert_eq!(
+      handler.handle(
+        &mut vcpu,
+        &mut frame,
+        sysreg(SysRegEncoding::new(3, 0, 1, 0, 0), 1, true)
+      ),
+      SysRegTrapResult::Unhandled
+    );
+    assert_eq!(frame.x[1], 7);
+    assert_eq!(frame.elr, 0);
+  }
+}